@@ -0,0 +1,62 @@
+use std::env;
+use std::str::FromStr;
+
+// TODO: find a good window / sim size
+pub const DEFAULT_WIDTH: usize = 720;
+pub const DEFAULT_HEIGHT: usize = 480;
+pub const DEFAULT_UNDO_MEMORY: usize = 256; // Megabytes
+pub const SAVE_FILE: &str = "save.pwrs"; // Quick save, in the working directory
+
+/// Settings chosen at startup
+pub struct Config {
+	pub width:       usize, // Sim size in pixels, the window starts at the same size
+	pub height:      usize,
+	pub threads:     Option<usize>,  // All cores are used when None
+	pub undo_memory: usize,          // Megabytes kept for undo and redo
+	pub import:      Option<String>  // TPT save loaded at startup
+}
+
+impl Config {
+	/// Reads `--width <px>`, `--height <px>`, `--threads <n>`, `--undo-memory <MB>` and `--import <file>` args
+	/// Invalid arguments are reported and ignored, browsers always get the defaults
+	pub fn from_args() -> Self {
+		let mut config = Self {
+			width:       DEFAULT_WIDTH,
+			height:      DEFAULT_HEIGHT,
+			threads:     None,
+			undo_memory: DEFAULT_UNDO_MEMORY,
+			import:      None
+		};
+
+		let mut args = env::args().skip(1);
+		while let Some(arg) = args.next() {
+			match arg.as_str() {
+				"--width" => config.width = Self::num(&arg, args.next()).unwrap_or(config.width),
+				"--height" => config.height = Self::num(&arg, args.next()).unwrap_or(config.height),
+				"--threads" => config.threads = Self::num(&arg, args.next()).or(config.threads),
+				"--undo-memory" => {
+					config.undo_memory = Self::num(&arg, args.next()).unwrap_or(config.undo_memory)
+				}
+				"--import" => match args.next() {
+					Some(file) => config.import = Some(file),
+					None => eprintln!("{} needs a file", arg)
+				},
+				_ => eprintln!("Unknown argument {}", arg)
+			}
+		}
+
+		config
+	}
+
+	fn num<T: FromStr+PartialOrd+Default>(arg: &str, value: Option<String>) -> Option<T> {
+		let parsed = value
+			.as_deref()
+			.and_then(|v| v.parse::<T>().ok())
+			.filter(|v| *v > T::default());
+
+		if parsed.is_none() {
+			eprintln!("{} needs a number above 0", arg);
+		}
+		parsed
+	}
+}
@@ -16,7 +16,7 @@ use crate::input::events::invoker::InputEventInvoker;
 use crate::rendering::gui::game_gui::GameGUI;
 use crate::rendering::renderer::Renderer;
 use crate::rendering::{Core, Rect};
-use crate::simulation::sim::{Simulation, WINH, WINW, XRES, YRES};
+use crate::simulation::sim::Simulation;
 
 pub struct InputData {
 	pub mouse_buttons:      HashMap<MouseButton, bool>,
@@ -128,9 +128,14 @@ pub async fn handle_events(
 			Event::AboutToWait => {
 				// TODO: Clean this up
 				let win_size: PhysicalSize<u32>;
+				let base_size: PhysicalSize<u32>;
 				{
-					win_size = ren.rendering_core.borrow().window_size;
+					let core = ren.rendering_core.borrow();
+					win_size = core.window_size;
+					base_size = core.base_size;
 				}
+				let (base_w, base_h) = (base_size.width as f32, base_size.height as f32);
+				let (sim_w, sim_h) = (sim.get_width(), sim.get_height());
 
 				let mouse_pos = Vector4 {
 					x: input.mouse_pos.x as f32,
@@ -139,8 +144,8 @@ pub async fn handle_events(
 					w: 1.0
 				};
 				let scale_factor = Vector2::new(
-					win_size.width as f32 / WINW as f32,
-					win_size.height as f32 / WINH as f32
+					win_size.width as f32 / base_w,
+					win_size.height as f32 / base_h
 				);
 
 				input.mouse_screen_pos = Vector4 {
@@ -152,16 +157,16 @@ pub async fn handle_events(
 
 				#[rustfmt::skip]
 				let mouse_pos =
-					Matrix4::from_translation( Vector3 { x: (WINW as f32 / 2.0), y: (WINH as f32 / 2.0), z: 0.0 }) *
+					Matrix4::from_translation( Vector3 { x: (base_w / 2.0), y: (base_h / 2.0), z: 0.0 }) *
 					ren.get_view_matrix().inverse_transform().unwrap() *
-					Matrix4::from_translation(-Vector3 { x: (WINW as f32 / 2.0), y: (WINH as f32 / 2.0), z: 0.0,}) *
+					Matrix4::from_translation(-Vector3 { x: (base_w / 2.0), y: (base_h / 2.0), z: 0.0,}) *
 					input.mouse_screen_pos;
 				input.mouse_pos_vector = mouse_pos;
 
 				let (mut cursor_x, mut cursor_y) = (mouse_pos.x as usize, mouse_pos.y as usize);
 				let mut hs = gui.brush_size as usize / 2usize;
-				cursor_x = cursor_x.clamp(hs, XRES - hs - (gui.brush_size % 2) as usize);
-				cursor_y = cursor_y.clamp(hs, YRES - hs - (gui.brush_size % 2) as usize);
+				cursor_x = cursor_x.clamp(hs, sim_w - hs - (gui.brush_size % 2) as usize);
+				cursor_y = cursor_y.clamp(hs, sim_h - hs - (gui.brush_size % 2) as usize);
 				input.cursor_pos = Vector2::new(cursor_x, cursor_y);
 
 				invoker.invoke(&mut sim, &mut ren, &mut gui, &mut input);
@@ -184,8 +189,8 @@ pub async fn handle_events(
 
 				// Clamp again because brush_size can be modified in invoker.invoke()
 				hs = gui.brush_size as usize / 2usize;
-				cursor_x = cursor_x.clamp(hs, XRES - hs - (gui.brush_size % 2) as usize);
-				cursor_y = cursor_y.clamp(hs, YRES - hs - (gui.brush_size % 2) as usize);
+				cursor_x = cursor_x.clamp(hs, sim_w - hs - (gui.brush_size % 2) as usize);
+				cursor_y = cursor_y.clamp(hs, sim_h - hs - (gui.brush_size % 2) as usize);
 				input.cursor_pos = Vector2::new(cursor_x, cursor_y);
 
 				gui.cursor = (
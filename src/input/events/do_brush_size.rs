@@ -30,7 +30,7 @@ impl InputEvent for DoBrushSize {
 
 	fn handle(
 		&self,
-		_sim: &mut Simulation,
+		sim: &mut Simulation,
 		_ren: &mut Renderer,
 		gui: &mut GameGUI,
 		input: &mut InputData
@@ -40,8 +40,10 @@ impl InputEvent for DoBrushSize {
 			if input.key_pressed(&PhysicalKey::Code(KeyCode::ShiftLeft)) {
 				speed = 2;
 			}
+			// Brush has to fit on the board
+			let max = sim.get_width().min(sim.get_height()).min(40) as i32;
 			gui.brush_size =
-				(gui.brush_size as i32 + input.scroll.signum() as i32 * speed).clamp(1, 40) as u32;
+				(gui.brush_size as i32 + input.scroll.signum() as i32 * speed).clamp(1, max) as u32;
 		}
 	}
 }
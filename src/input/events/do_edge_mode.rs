@@ -0,0 +1,39 @@
+use log::info;
+use winit::keyboard::{KeyCode, PhysicalKey};
+
+use crate::input::event_handling::InputData;
+use crate::input::events::input_event::{AnyKey, InputEvent, KeyEvent, KeyState};
+use crate::rendering::gui::game_gui::GameGUI;
+use crate::rendering::renderer::Renderer;
+use crate::simulation::sim::{EdgeMode, Simulation};
+
+pub struct DoEdgeMode {}
+
+impl InputEvent for DoEdgeMode {
+	fn get_name(&self) -> String {
+		String::from("DoEdgeMode")
+	}
+
+	fn default_keys(&self) -> Vec<KeyEvent> {
+		vec![KeyEvent {
+			key:              AnyKey::Keyboard(PhysicalKey::Code(KeyCode::KeyE)),
+			state:            KeyState::Pressed,
+			combine_previous: None
+		}]
+	}
+
+	fn handle(
+		&self,
+		sim: &mut Simulation,
+		_ren: &mut Renderer,
+		_gui: &mut GameGUI,
+		_input: &mut InputData
+	) {
+		sim.edge_mode = match sim.edge_mode {
+			EdgeMode::Void => EdgeMode::Solid,
+			EdgeMode::Solid => EdgeMode::Wrap,
+			EdgeMode::Wrap => EdgeMode::Void
+		};
+		info!("Edge mode: {:?}", sim.edge_mode);
+	}
+}
@@ -0,0 +1,58 @@
+use log::info;
+use winit::keyboard::{KeyCode, PhysicalKey};
+
+use crate::input::event_handling::InputData;
+use crate::input::events::input_event::{AnyKey, InputEvent, KeyEvent, KeyState};
+use crate::rendering::gui::game_gui::GameGUI;
+use crate::rendering::renderer::Renderer;
+use crate::simulation::elements::{EL_NONE, EL_SPRK};
+use crate::simulation::sim::Simulation;
+
+/// Scrolling while Q is held picks the element placed with LMB
+pub struct DoElementSelect {}
+
+impl InputEvent for DoElementSelect {
+	fn get_name(&self) -> String {
+		String::from("DoElementSelect")
+	}
+
+	fn default_keys(&self) -> Vec<KeyEvent> {
+		vec![KeyEvent {
+			key:              AnyKey::Keyboard(PhysicalKey::Code(KeyCode::KeyQ)),
+			state:            KeyState::Held,
+			combine_previous: None
+		}]
+	}
+
+	fn handle(
+		&self,
+		sim: &mut Simulation,
+		_ren: &mut Renderer,
+		gui: &mut GameGUI,
+		input: &mut InputData
+	) {
+		if input.scroll != 0.0 {
+			// Sparks are made with the spark tool instead
+			let elements: Vec<u16> = sim
+				.element_manager
+				.elements
+				.iter()
+				.map(|el| el.id)
+				.filter(|id| *id != EL_NONE.id && *id != EL_SPRK.id)
+				.collect();
+
+			let current = elements
+				.iter()
+				.position(|id| *id == gui.element)
+				.unwrap_or(0) as i32;
+			let next = current + input.scroll.clamp(-1f32, 1f32) as i32;
+			gui.element = elements[next.rem_euclid(elements.len() as i32) as usize];
+			info!(
+				"Element: {}",
+				sim.element_manager.elements[gui.element as usize].name
+			);
+
+			input.scroll = 0.0; // capture scroll
+		}
+	}
+}
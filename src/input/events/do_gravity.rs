@@ -0,0 +1,81 @@
+use std::cell::Cell;
+
+use log::info;
+use winit::keyboard::{KeyCode, PhysicalKey};
+
+use crate::input::event_handling::InputData;
+use crate::input::events::input_event::{AnyKey, InputEvent, KeyEvent, KeyState, LogicalOperator};
+use crate::rendering::gui::game_gui::GameGUI;
+use crate::rendering::renderer::Renderer;
+use crate::simulation::gravity::GravityMode;
+use crate::simulation::sim::Simulation;
+
+const STRENGTH_STEP: f32 = 0.01;
+const MAX_STRENGTH: f32 = 1.0;
+
+/// Releasing R switches the gravity mode, scrolling while R is held changes the strength instead
+pub struct DoGravity {
+	scrolled: Cell<bool> // Strength was changed since R got pressed
+}
+
+impl DoGravity {
+	pub fn new() -> Self {
+		Self {
+			scrolled: Cell::new(false)
+		}
+	}
+}
+
+impl InputEvent for DoGravity {
+	fn get_name(&self) -> String {
+		String::from("DoGravity")
+	}
+
+	fn default_keys(&self) -> Vec<KeyEvent> {
+		vec![
+			KeyEvent {
+				key:              AnyKey::Keyboard(PhysicalKey::Code(KeyCode::KeyR)),
+				state:            KeyState::Held,
+				combine_previous: None
+			},
+			KeyEvent {
+				key:              AnyKey::Keyboard(PhysicalKey::Code(KeyCode::KeyR)),
+				state:            KeyState::Released,
+				combine_previous: Some(LogicalOperator::Or)
+			},
+		]
+	}
+
+	fn handle(
+		&self,
+		sim: &mut Simulation,
+		_ren: &mut Renderer,
+		_gui: &mut GameGUI,
+		input: &mut InputData
+	) {
+		let key = PhysicalKey::Code(KeyCode::KeyR);
+
+		if input.key_pressed(&key) && input.scroll != 0.0 {
+			let change = input.scroll.clamp(-1f32, 1f32) * STRENGTH_STEP;
+			let strength = sim.gravity.strength + change;
+			sim.gravity.strength = strength.clamp(-MAX_STRENGTH, MAX_STRENGTH);
+			info!("Gravity strength: {:.2}", sim.gravity.strength);
+
+			self.scrolled.set(true);
+			input.scroll = 0.0; // capture scroll
+		}
+
+		if input.key_just_released(&key) {
+			if !self.scrolled.get() {
+				sim.gravity.mode = match sim.gravity.mode {
+					GravityMode::Off => GravityMode::Vertical,
+					GravityMode::Vertical => GravityMode::Radial,
+					GravityMode::Radial => GravityMode::Newtonian,
+					GravityMode::Newtonian => GravityMode::Off
+				};
+				info!("Gravity mode: {:?}", sim.gravity.mode);
+			}
+			self.scrolled.set(false);
+		}
+	}
+}
@@ -1,13 +1,12 @@
 use cgmath::num_traits::pow;
 use winit::event::MouseButton;
+use winit::keyboard::{KeyCode, PhysicalKey};
 
 use crate::input::event_handling::InputData;
-use crate::input::events::input_event::{AnyKey, InputEvent, KeyEvent, KeyState};
+use crate::input::events::input_event::{AnyKey, InputEvent, KeyEvent, KeyState, LogicalOperator};
 use crate::rendering::gui::game_gui::GameGUI;
 use crate::rendering::renderer::Renderer;
-use crate::simulation::elements::EL_DUST;
 use crate::simulation::sim::Simulation;
-use crate::simulation::Particle;
 
 pub struct DoLmbTool {}
 
@@ -17,11 +16,24 @@ impl InputEvent for DoLmbTool {
 	}
 
 	fn default_keys(&self) -> Vec<KeyEvent> {
-		vec![KeyEvent {
-			key:              AnyKey::Mouse(MouseButton::Left),
-			state:            KeyState::Held,
-			combine_previous: None
-		}]
+		vec![
+			KeyEvent {
+				key:              AnyKey::Mouse(MouseButton::Left),
+				state:            KeyState::Held,
+				combine_previous: None
+			},
+			// Held W switches the brush to walls, held S to sparks
+			KeyEvent {
+				key:              AnyKey::Keyboard(PhysicalKey::Code(KeyCode::KeyW)),
+				state:            KeyState::NotHeld,
+				combine_previous: Some(LogicalOperator::And)
+			},
+			KeyEvent {
+				key:              AnyKey::Keyboard(PhysicalKey::Code(KeyCode::KeyS)),
+				state:            KeyState::NotHeld,
+				combine_previous: Some(LogicalOperator::And)
+			},
+		]
 	}
 
 	fn handle(
@@ -31,17 +43,18 @@ impl InputEvent for DoLmbTool {
 		gui: &mut GameGUI,
 		input: &mut InputData
 	) {
+		// Saved at the start of the stroke, so undo takes back the whole stroke
+		if input.mouse_just_pressed(&MouseButton::Left) {
+			gui.history.record(sim);
+		}
+
 		let size = gui.brush_size as usize;
 		let hs = size / 2;
 		let (x, y) = (input.cursor_pos.x, input.cursor_pos.y);
 
 		for i in 0..pow(size, 2) {
 			// TODO: actual tools instead of hardcoded stuff, same for RMB
-			sim.add_part(Particle::new(
-				EL_DUST.id,
-				(x - hs + i / size) as f32,
-				(y - hs + i % size) as f32
-			));
+			sim.create_part(gui.element, x - hs + i / size, y - hs + i % size);
 		}
 	}
 }
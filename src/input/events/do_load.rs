@@ -0,0 +1,64 @@
+use log::{error, info};
+use winit::keyboard::{KeyCode, PhysicalKey};
+
+use crate::config::SAVE_FILE;
+use crate::input::event_handling::InputData;
+use crate::input::events::input_event::{AnyKey, InputEvent, KeyEvent, KeyState, LogicalOperator};
+use crate::rendering::gui::game_gui::GameGUI;
+use crate::rendering::renderer::Renderer;
+use crate::simulation::sim::Simulation;
+
+/// Ctrl + O loads the board from `SAVE_FILE`, it can be undone
+pub struct DoLoad {}
+
+impl InputEvent for DoLoad {
+	fn get_name(&self) -> String {
+		String::from("DoLoad")
+	}
+
+	fn default_keys(&self) -> Vec<KeyEvent> {
+		vec![
+			KeyEvent {
+				key:              AnyKey::Keyboard(PhysicalKey::Code(KeyCode::ControlLeft)),
+				state:            KeyState::Held,
+				combine_previous: None
+			},
+			KeyEvent {
+				key:              AnyKey::Keyboard(PhysicalKey::Code(KeyCode::ControlRight)),
+				state:            KeyState::Held,
+				combine_previous: Some(LogicalOperator::Or)
+			},
+			KeyEvent {
+				key:              AnyKey::Keyboard(PhysicalKey::Code(KeyCode::KeyO)),
+				state:            KeyState::Pressed,
+				combine_previous: Some(LogicalOperator::And)
+			},
+		]
+	}
+
+	fn handle(
+		&self,
+		sim: &mut Simulation,
+		_ren: &mut Renderer,
+		gui: &mut GameGUI,
+		_input: &mut InputData
+	) {
+		let data = match std::fs::read(SAVE_FILE) {
+			Ok(data) => data,
+			Err(e) => {
+				error!("Couldn't read {}: {}", SAVE_FILE, e);
+				return;
+			}
+		};
+
+		// Only a load that went through can be undone
+		let before = sim.snapshot();
+		match sim.load(&data) {
+			Ok(_) => {
+				gui.history.record_snapshot(before);
+				info!("Loaded {}", SAVE_FILE);
+			}
+			Err(e) => error!("Couldn't load {}: {}", SAVE_FILE, e)
+		}
+	}
+}
@@ -0,0 +1,49 @@
+use log::info;
+use winit::keyboard::{KeyCode, PhysicalKey};
+
+use crate::input::event_handling::InputData;
+use crate::input::events::input_event::{AnyKey, InputEvent, KeyEvent, KeyState, LogicalOperator};
+use crate::rendering::gui::game_gui::GameGUI;
+use crate::rendering::renderer::Renderer;
+use crate::simulation::sim::Simulation;
+
+/// Ctrl + Y brings back what was undone
+pub struct DoRedo {}
+
+impl InputEvent for DoRedo {
+	fn get_name(&self) -> String {
+		String::from("DoRedo")
+	}
+
+	fn default_keys(&self) -> Vec<KeyEvent> {
+		vec![
+			KeyEvent {
+				key:              AnyKey::Keyboard(PhysicalKey::Code(KeyCode::ControlLeft)),
+				state:            KeyState::Held,
+				combine_previous: None
+			},
+			KeyEvent {
+				key:              AnyKey::Keyboard(PhysicalKey::Code(KeyCode::ControlRight)),
+				state:            KeyState::Held,
+				combine_previous: Some(LogicalOperator::Or)
+			},
+			KeyEvent {
+				key:              AnyKey::Keyboard(PhysicalKey::Code(KeyCode::KeyY)),
+				state:            KeyState::Pressed,
+				combine_previous: Some(LogicalOperator::And)
+			},
+		]
+	}
+
+	fn handle(
+		&self,
+		sim: &mut Simulation,
+		_ren: &mut Renderer,
+		gui: &mut GameGUI,
+		_input: &mut InputData
+	) {
+		if !gui.history.redo(sim) {
+			info!("Nothing to redo");
+		}
+	}
+}
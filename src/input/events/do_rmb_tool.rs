@@ -1,8 +1,9 @@
 use cgmath::num_traits::pow;
 use winit::event::MouseButton;
+use winit::keyboard::{KeyCode, PhysicalKey};
 
 use crate::input::event_handling::InputData;
-use crate::input::events::input_event::{AnyKey, InputEvent, KeyEvent, KeyState};
+use crate::input::events::input_event::{AnyKey, InputEvent, KeyEvent, KeyState, LogicalOperator};
 use crate::rendering::gui::game_gui::GameGUI;
 use crate::rendering::renderer::Renderer;
 use crate::simulation::sim::Simulation;
@@ -15,11 +16,19 @@ impl InputEvent for DoRmbTool {
 	}
 
 	fn default_keys(&self) -> Vec<KeyEvent> {
-		vec![KeyEvent {
-			key:              AnyKey::Mouse(MouseButton::Right),
-			state:            KeyState::Held,
-			combine_previous: None
-		}]
+		vec![
+			KeyEvent {
+				key:              AnyKey::Mouse(MouseButton::Right),
+				state:            KeyState::Held,
+				combine_previous: None
+			},
+			// Held W switches the brush to walls
+			KeyEvent {
+				key:              AnyKey::Keyboard(PhysicalKey::Code(KeyCode::KeyW)),
+				state:            KeyState::NotHeld,
+				combine_previous: Some(LogicalOperator::And)
+			},
+		]
 	}
 
 	fn handle(
@@ -29,6 +38,10 @@ impl InputEvent for DoRmbTool {
 		gui: &mut GameGUI,
 		input: &mut InputData
 	) {
+		if input.mouse_just_pressed(&MouseButton::Right) {
+			gui.history.record(sim);
+		}
+
 		let size = gui.brush_size as usize;
 		let hs = size / 2;
 		let (x, y) = (input.cursor_pos.x, input.cursor_pos.y);
@@ -36,7 +49,7 @@ impl InputEvent for DoRmbTool {
 		for i in 0..pow(size, 2) {
 			let val = sim.get_id(x - hs + i % size, y - hs + i / size);
 			if let Some(part_id) = val {
-				sim.kill_part(part_id).expect("Tried to kill invalid part");
+				sim.kill_part(part_id);
 			}
 		}
 	}
@@ -0,0 +1,51 @@
+use log::{error, info};
+use winit::keyboard::{KeyCode, PhysicalKey};
+
+use crate::config::SAVE_FILE;
+use crate::input::event_handling::InputData;
+use crate::input::events::input_event::{AnyKey, InputEvent, KeyEvent, KeyState, LogicalOperator};
+use crate::rendering::gui::game_gui::GameGUI;
+use crate::rendering::renderer::Renderer;
+use crate::simulation::sim::Simulation;
+
+/// Ctrl + S saves the board to `SAVE_FILE`
+pub struct DoSave {}
+
+impl InputEvent for DoSave {
+	fn get_name(&self) -> String {
+		String::from("DoSave")
+	}
+
+	fn default_keys(&self) -> Vec<KeyEvent> {
+		vec![
+			KeyEvent {
+				key:              AnyKey::Keyboard(PhysicalKey::Code(KeyCode::ControlLeft)),
+				state:            KeyState::Held,
+				combine_previous: None
+			},
+			KeyEvent {
+				key:              AnyKey::Keyboard(PhysicalKey::Code(KeyCode::ControlRight)),
+				state:            KeyState::Held,
+				combine_previous: Some(LogicalOperator::Or)
+			},
+			KeyEvent {
+				key:              AnyKey::Keyboard(PhysicalKey::Code(KeyCode::KeyS)),
+				state:            KeyState::Pressed,
+				combine_previous: Some(LogicalOperator::And)
+			},
+		]
+	}
+
+	fn handle(
+		&self,
+		sim: &mut Simulation,
+		_ren: &mut Renderer,
+		_gui: &mut GameGUI,
+		_input: &mut InputData
+	) {
+		match std::fs::write(SAVE_FILE, sim.save()) {
+			Ok(_) => info!("Saved to {}", SAVE_FILE),
+			Err(e) => error!("Couldn't save to {}: {}", SAVE_FILE, e)
+		}
+	}
+}
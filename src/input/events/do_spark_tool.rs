@@ -0,0 +1,53 @@
+use cgmath::num_traits::pow;
+use winit::event::MouseButton;
+use winit::keyboard::{KeyCode, PhysicalKey};
+
+use crate::input::event_handling::InputData;
+use crate::input::events::input_event::{AnyKey, InputEvent, KeyEvent, KeyState, LogicalOperator};
+use crate::rendering::gui::game_gui::GameGUI;
+use crate::rendering::renderer::Renderer;
+use crate::simulation::sim::Simulation;
+
+/// Sparks conductors under the brush
+pub struct DoSparkTool {}
+
+impl InputEvent for DoSparkTool {
+	fn get_name(&self) -> String {
+		String::from("DoSparkTool")
+	}
+
+	fn default_keys(&self) -> Vec<KeyEvent> {
+		vec![
+			KeyEvent {
+				key:              AnyKey::Keyboard(PhysicalKey::Code(KeyCode::KeyS)),
+				state:            KeyState::Held,
+				combine_previous: None
+			},
+			KeyEvent {
+				key:              AnyKey::Mouse(MouseButton::Left),
+				state:            KeyState::Held,
+				combine_previous: Some(LogicalOperator::And)
+			},
+		]
+	}
+
+	fn handle(
+		&self,
+		sim: &mut Simulation,
+		_ren: &mut Renderer,
+		gui: &mut GameGUI,
+		input: &mut InputData
+	) {
+		if input.mouse_just_pressed(&MouseButton::Left) {
+			gui.history.record(sim);
+		}
+
+		let size = gui.brush_size as usize;
+		let hs = size / 2;
+		let (x, y) = (input.cursor_pos.x, input.cursor_pos.y);
+
+		for i in 0..pow(size, 2) {
+			sim.spark(x - hs + i % size, y - hs + i / size);
+		}
+	}
+}
@@ -0,0 +1,49 @@
+use log::info;
+use winit::keyboard::{KeyCode, PhysicalKey};
+
+use crate::input::event_handling::InputData;
+use crate::input::events::input_event::{AnyKey, InputEvent, KeyEvent, KeyState, LogicalOperator};
+use crate::rendering::gui::game_gui::GameGUI;
+use crate::rendering::renderer::Renderer;
+use crate::simulation::sim::Simulation;
+
+/// Ctrl + Z takes back the last stroke
+pub struct DoUndo {}
+
+impl InputEvent for DoUndo {
+	fn get_name(&self) -> String {
+		String::from("DoUndo")
+	}
+
+	fn default_keys(&self) -> Vec<KeyEvent> {
+		vec![
+			KeyEvent {
+				key:              AnyKey::Keyboard(PhysicalKey::Code(KeyCode::ControlLeft)),
+				state:            KeyState::Held,
+				combine_previous: None
+			},
+			KeyEvent {
+				key:              AnyKey::Keyboard(PhysicalKey::Code(KeyCode::ControlRight)),
+				state:            KeyState::Held,
+				combine_previous: Some(LogicalOperator::Or)
+			},
+			KeyEvent {
+				key:              AnyKey::Keyboard(PhysicalKey::Code(KeyCode::KeyZ)),
+				state:            KeyState::Pressed,
+				combine_previous: Some(LogicalOperator::And)
+			},
+		]
+	}
+
+	fn handle(
+		&self,
+		sim: &mut Simulation,
+		_ren: &mut Renderer,
+		gui: &mut GameGUI,
+		_input: &mut InputData
+	) {
+		if !gui.history.undo(sim) {
+			info!("Nothing to undo");
+		}
+	}
+}
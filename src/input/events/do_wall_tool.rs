@@ -0,0 +1,71 @@
+use cgmath::num_traits::pow;
+use log::info;
+use winit::event::MouseButton;
+use winit::keyboard::{KeyCode, PhysicalKey};
+
+use crate::input::event_handling::InputData;
+use crate::input::events::input_event::{AnyKey, InputEvent, KeyEvent, KeyState};
+use crate::rendering::gui::game_gui::GameGUI;
+use crate::rendering::renderer::Renderer;
+use crate::simulation::sim::Simulation;
+use crate::simulation::walls::WallType;
+
+/// While W is held LMB places walls, RMB erases them and scrolling picks the wall type
+pub struct DoWallTool {}
+
+impl InputEvent for DoWallTool {
+	fn get_name(&self) -> String {
+		String::from("DoWallTool")
+	}
+
+	fn default_keys(&self) -> Vec<KeyEvent> {
+		vec![KeyEvent {
+			key:              AnyKey::Keyboard(PhysicalKey::Code(KeyCode::KeyW)),
+			state:            KeyState::Held,
+			combine_previous: None
+		}]
+	}
+
+	fn handle(
+		&self,
+		sim: &mut Simulation,
+		_ren: &mut Renderer,
+		gui: &mut GameGUI,
+		input: &mut InputData
+	) {
+		if input.scroll != 0.0 {
+			// WallType::None is the eraser, so it's left out
+			let types = &WallType::ALL[1..];
+			let current = types.iter().position(|w| *w == gui.wall_type).unwrap_or(0) as i32;
+			let next = current + input.scroll.clamp(-1f32, 1f32) as i32;
+			gui.wall_type = types[next.rem_euclid(types.len() as i32) as usize];
+			info!("Wall type: {:?}", gui.wall_type);
+
+			input.scroll = 0.0; // capture scroll
+		}
+
+		let wall = if input.mouse_pressed(&MouseButton::Left) {
+			gui.wall_type
+		}
+		else if input.mouse_pressed(&MouseButton::Right) {
+			WallType::None
+		}
+		else {
+			return;
+		};
+
+		if input.mouse_just_pressed(&MouseButton::Left) ||
+			input.mouse_just_pressed(&MouseButton::Right)
+		{
+			gui.history.record(sim);
+		}
+
+		let size = gui.brush_size as usize;
+		let hs = size / 2;
+		let (x, y) = (input.cursor_pos.x, input.cursor_pos.y);
+
+		for i in 0..pow(size, 2) {
+			sim.set_wall(x - hs + i % size, y - hs + i / size, wall);
+		}
+	}
+}
@@ -5,7 +5,7 @@ use crate::input::event_handling::InputData;
 use crate::input::events::input_event::{AnyKey, InputEvent, KeyEvent, KeyState};
 use crate::rendering::gui::game_gui::GameGUI;
 use crate::rendering::renderer::Renderer;
-use crate::simulation::sim::{Simulation, WINH, WINW};
+use crate::simulation::sim::Simulation;
 
 pub struct DoZoom {}
 
@@ -38,13 +38,16 @@ impl InputEvent for DoZoom {
 			zoom = zoom.clamp(1.0, 50.0);
 			ren.set_zoom(zoom);
 
+			let base_size = ren.rendering_core.borrow().base_size;
+			let (hw, hh) = (base_size.width as f32 / 2.0, base_size.height as f32 / 2.0);
+
 			#[rustfmt::skip]
                 let res =
-                    Matrix4::from_translation( Vector3 { x: (WINW / 2) as f32, y: (WINH / 2) as f32, z: 0.0 }) *
+                    Matrix4::from_translation( Vector3 { x: hw,                y: hh,                z: 0.0 }) *
                     Matrix4::from_translation(-Vector3 { x: ren.get_pan().x,   y: ren.get_pan().y,	 z: 0.0 }) *
                     Matrix4::from_scale(prev_zoom / zoom) *
                     Matrix4::from_translation( Vector3 { x: ren.get_pan().x,   y: ren.get_pan().y,	 z: 0.0 }) *
-                    Matrix4::from_translation(-Vector3 { x: (WINW / 2) as f32, y: (WINH / 2) as f32, z: 0.0 }) *
+                    Matrix4::from_translation(-Vector3 { x: hw,                y: hh,                z: 0.0 }) *
                     input.mouse_pos_vector;
 
 			ren.set_pan(ren.get_pan() + (res - input.mouse_pos_vector).truncate().truncate());
@@ -3,12 +3,21 @@ use log::warn;
 use crate::input::event_handling::InputData;
 use crate::input::events::do_brush_size::DoBrushSize;
 use crate::input::events::do_camera_center::DoCameraCenter;
+use crate::input::events::do_edge_mode::DoEdgeMode;
+use crate::input::events::do_element_select::DoElementSelect;
+use crate::input::events::do_gravity::DoGravity;
 use crate::input::events::do_grid_size::DoGridSize;
 use crate::input::events::do_lmb_tool::DoLmbTool;
+use crate::input::events::do_load::DoLoad;
 use crate::input::events::do_pan::DoPan;
 use crate::input::events::do_pause::DoPause;
+use crate::input::events::do_redo::DoRedo;
 use crate::input::events::do_rmb_tool::DoRmbTool;
+use crate::input::events::do_save::DoSave;
+use crate::input::events::do_spark_tool::DoSparkTool;
 use crate::input::events::do_tick::DoTick;
+use crate::input::events::do_undo::DoUndo;
+use crate::input::events::do_wall_tool::DoWallTool;
 use crate::input::events::do_zoom::DoZoom;
 use crate::input::events::input_event::{AnyKey, InputEvent, KeyState, LogicalOperator};
 use crate::rendering::gui::game_gui::GameGUI;
@@ -27,11 +36,20 @@ impl InputEventInvoker {
 				Box::from(DoPause {}),
 				Box::from(DoLmbTool {}),
 				Box::from(DoRmbTool {}),
+				Box::from(DoSparkTool {}),
 				Box::from(DoPan::new()),
 				Box::from(DoGridSize {}),
 				Box::from(DoZoom {}),
+				Box::from(DoWallTool {}),
+				Box::from(DoElementSelect {}),
+				Box::from(DoGravity::new()),
 				Box::from(DoCameraCenter {}),
 				Box::from(DoBrushSize {}),
+				Box::from(DoEdgeMode {}),
+				Box::from(DoUndo {}),
+				Box::from(DoRedo {}),
+				Box::from(DoSave {}),
+				Box::from(DoLoad {}),
 			]
 		}
 	}
@@ -1,11 +1,20 @@
 mod do_brush_size;
 mod do_camera_center;
+mod do_edge_mode;
+mod do_element_select;
+mod do_gravity;
 mod do_grid_size;
 mod do_lmb_tool;
+mod do_load;
 mod do_pan;
 mod do_pause;
+mod do_redo;
 mod do_rmb_tool;
+mod do_save;
+mod do_spark_tool;
 mod do_tick;
+mod do_undo;
+mod do_wall_tool;
 mod do_zoom;
 mod input_event;
 pub mod invoker;
@@ -1,8 +1,10 @@
 extern crate core;
 
+mod config;
 mod input;
 mod rendering;
-mod simulation;
+// Doesn't depend on the window or renderer, so it can be used on its own
+pub mod simulation;
 
 use std::collections::HashMap;
 use std::rc::Rc;
@@ -10,22 +12,34 @@ use std::rc::Rc;
 use cgmath::{Vector2, Vector4, Zero};
 #[cfg(target_arch = "wasm32")]
 use wasm_bindgen::prelude::*;
-use winit::dpi::PhysicalPosition;
+use winit::dpi::{PhysicalPosition, PhysicalSize};
 
+use crate::config::Config;
 use crate::input::event_handling::{handle_events, InputData};
 use crate::rendering::gui::game_gui::GameGUI;
 use crate::rendering::renderer::Renderer;
 use crate::simulation::elements::EL_BRCK;
-use crate::simulation::sim::{Simulation, WINH, WINW};
+use crate::simulation::history::History;
+use crate::simulation::sim::Simulation;
 use crate::simulation::Particle;
 
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen(start))]
 pub async fn run() {
-	let mut sim = Simulation::new();
-	let ren = Renderer::new().await;
+	let config = Config::from_args();
+	let mut sim = Simulation::new(config.width, config.height);
+	if let Some(threads) = config.threads {
+		sim.set_threads(threads);
+	}
+
+	let ren = Renderer::new(PhysicalSize::new(config.width as u32, config.height as u32)).await;
 	let event_loop = ren.1;
 	let ren = ren.0;
-	let gui = GameGUI::new(Rc::clone(&ren.rendering_core));
+	let history = History::new(config.undo_memory.saturating_mul(1024 * 1024));
+	let mut gui = GameGUI::new(Rc::clone(&ren.rendering_core), history);
+	// Brush has to fit on the board
+	gui.brush_size = gui
+		.brush_size
+		.min(sim.get_width().min(sim.get_height()) as u32);
 
 	let input: InputData = InputData {
 		// TODO: Ton of things here should be elsewhere
@@ -40,31 +54,47 @@ pub async fn run() {
 		cursor_pos:         Vector2::zero()
 	};
 
-	for i in 0..100 {
-		sim.add_part(Particle::new(EL_BRCK.id, i + 20, i + 50));
-		sim.add_part(Particle::new(EL_BRCK.id, i + 20, i + 70));
+	let (w, h) = (sim.get_width(), sim.get_height());
+	// Demo layout reaches x 310 and y 169, smaller boards start empty
+	if w > 310 && h >= 170 {
+		for i in 0..100 {
+			sim.add_part(Particle::new(EL_BRCK.id, i + 20, i + 50));
+			sim.add_part(Particle::new(EL_BRCK.id, i + 20, i + 70));
 
-		sim.add_part(Particle::new(EL_BRCK.id, i + 150, i + 50));
+			sim.add_part(Particle::new(EL_BRCK.id, i + 150, i + 50));
 
-		let y = (WINH as f32 * 0.8) as u16;
-		sim.add_part(Particle::new(EL_BRCK.id, 10, y - i));
-		sim.add_part(Particle::new(EL_BRCK.id, 310, y - i));
-	}
+			let y = (h as f32 * 0.8) as u16;
+			sim.add_part(Particle::new(EL_BRCK.id, 10, y - i));
+			sim.add_part(Particle::new(EL_BRCK.id, 310, y - i));
+		}
 
-	for i in 0..300 {
-		sim.add_part(Particle::new(
-			EL_BRCK.id,
-			i + 10,
-			(WINH as f32 * 0.8) as u16
-		));
+		for i in 0..300 {
+			sim.add_part(Particle::new(EL_BRCK.id, i + 10, (h as f32 * 0.8) as u16));
+		}
+
+		sim.add_part(Particle::new(EL_BRCK.id, (w / 2) as u16, (h / 2) as u16));
 	}
 
-	sim.add_part(Particle::new(
-		EL_BRCK.id,
-		(WINW / 2) as u16,
-		(WINH / 2) as u16
-	));
+	if let Some(file) = &config.import {
+		import_tpt_save(&mut sim, file);
+	}
 
 	let rendering_core = ren.rendering_core.clone();
 	handle_events(event_loop, input, sim, ren, gui, rendering_core).await;
 }
+
+/// Replaces the board with a TPT save and prints what couldn't be imported
+fn import_tpt_save(sim: &mut Simulation, file: &str) {
+	let data = match std::fs::read(file) {
+		Ok(data) => data,
+		Err(e) => {
+			eprintln!("Couldn't read {}: {}", file, e);
+			return;
+		}
+	};
+
+	match sim.import_ops(&data) {
+		Ok(report) => println!("Imported {}\n{}", file, report),
+		Err(e) => eprintln!("Couldn't import {}: {}", file, e)
+	}
+}
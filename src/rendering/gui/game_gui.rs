@@ -9,19 +9,25 @@ use crate::rendering::gui::components::Component;
 use crate::rendering::gui::immediate_mode::gui_renderer::ImmediateGUI;
 use crate::rendering::render_utils::core::Core;
 use crate::rendering::Rect;
+use crate::simulation::elements::EL_DUST;
+use crate::simulation::history::History;
+use crate::simulation::walls::WallType;
 
 pub struct GameGUI<'a> {
 	pub immediate_gui: ImmediateGUI<'a>,
 	pub grid_size:     u32,
 	pub cursor:        Rect,
 	pub brush_size:    u32,
+	pub wall_type:     WallType,
+	pub element:       u16, // Placed with LMB
+	pub history:       History, // Undo and redo of brush strokes
 	pub gui_root:      Rc<RefCell<dyn Component>>,
 
 	pub fps_display: Rc<RefCell<FPSDisplay>>
 }
 
 impl GameGUI<'_> {
-	pub(crate) fn new(rendering_core: Rc<RefCell<Core>>) -> Self {
+	pub(crate) fn new(rendering_core: Rc<RefCell<Core>>, history: History) -> Self {
 		let mut gui = ImmediateGUI::new(rendering_core);
 		let root = Rc::new(RefCell::new(Root::new())) as Rc<RefCell<dyn Component>>;
 
@@ -36,6 +42,9 @@ impl GameGUI<'_> {
 			grid_size: 0,
 			cursor: (Vector2::zero(), Vector2::zero()),
 			brush_size: 5,
+			wall_type: WallType::Wall,
+			element: EL_DUST.id,
+			history,
 			gui_root: root
 		}
 	}
@@ -14,7 +14,6 @@ use crate::rendering::gui::immediate_mode::gui_vert::GUIVert;
 use crate::rendering::render_utils::core::Core;
 use crate::rendering::render_utils::pipeline::{Pipeline, PipelineDescriptor, Shader, ShaderType};
 use crate::rendering::render_utils::vertex_type::VertexType;
-use crate::simulation::sim::{WINH, WINW};
 
 #[repr(C)]
 #[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
@@ -318,21 +317,16 @@ impl ImmediateGUI {
 	pub fn draw_queued<'a>(&'a self, mut render_pass: RenderPass<'a>) {
 		let core = self.rendering_core.borrow();
 		let (w, h) = (core.window_size.width, core.window_size.height);
+		let (bw, bh) = (core.base_size.width as f32, core.base_size.height as f32);
 
-		self.window_scale_ratio.set(Vector2::from([
-			w as f32 / WINW as f32,
-			h as f32 / WINH as f32
-		]));
+		self.window_scale_ratio
+			.set(Vector2::from([w as f32 / bw, h as f32 / bh]));
 		self.pipeline.vert_num.set((self.rect_num * 6) as usize);
 
 		// Shapes transform
 		let transform = <Matrix4<f32> as Into<[[f32; 4]; 4]>>::into(
-			Matrix4::from_nonuniform_scale(2.0 / WINW as f32, -2.0 / WINH as f32, 1.0) *
-				Matrix4::from_translation(Vector3::from([
-					-(WINW as f32 / 2.0),
-					-(WINH as f32 / 2.0),
-					0.0
-				]))
+			Matrix4::from_nonuniform_scale(2.0 / bw, -2.0 / bh, 1.0) *
+				Matrix4::from_translation(Vector3::from([-(bw / 2.0), -(bh / 2.0), 0.0]))
 		);
 		let uniforms = GUIUniforms { transform };
 
@@ -7,6 +7,7 @@ pub struct Core {
 	pub instance:       wgpu::Instance,
 	pub window:         Window,
 	pub window_size:    PhysicalSize<u32>,
+	pub base_size:      PhysicalSize<u32>, // Initial window size, sim and GUI are laid out in it
 	pub surface:        wgpu::Surface,
 	pub surface_format: wgpu::TextureFormat,
 	pub device:         wgpu::Device,
@@ -123,6 +124,7 @@ impl Core {
 			instance,
 			window,
 			window_size,
+			base_size: window_size,
 			surface,
 			surface_format,
 			device,
@@ -17,7 +17,9 @@ use crate::rendering::render_utils::VertexType;
 use crate::rendering::texture_data::TextureData;
 use crate::rendering::timing::Timing;
 use crate::rendering::vert::Vert;
-use crate::simulation::sim::{Simulation, WINH, WINW, XRES, YRES};
+use crate::simulation::air::CELL;
+use crate::simulation::elements::ElementBehaviour;
+use crate::simulation::sim::Simulation;
 
 pub const OPENGL_TO_WGPU_MATRIX: Matrix4<f32> = Matrix4::new(
 	1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.5, 0.0, 0.0, 0.0, 0.5, 1.0
@@ -41,6 +43,8 @@ pub struct Renderer {
 	pub pipeline:       render_utils::Pipeline,
 	screen_texture:     render_utils::Texture,
 	gui_texture:        render_utils::Texture,
+	wall_texture:       render_utils::Texture,
+	wall_revision:      Option<u64>, // Revision of the walls in wall_texture
 
 	pub timings: Timing,
 
@@ -54,18 +58,14 @@ pub struct Renderer {
 }
 
 impl Renderer {
-	pub async fn new() -> (Self, EventLoop<()>) {
+	/// Size is the size of the simulation, the window starts with the same size
+	pub async fn new(size: PhysicalSize<u32>) -> (Self, EventLoop<()>) {
 		let event_loop = EventLoop::new().unwrap();
-		let rendering_core = render_utils::Core::new(
-			"PowderRS",
-			PhysicalSize::new(WINW as u32, WINH as u32),
-			&event_loop
-		)
-		.await;
+		let rendering_core = render_utils::Core::new("PowderRS", size, &event_loop).await;
 		rendering_core.window.set_resizable(true);
 		rendering_core.window.set_transparent(false); // (;
 
-		let (w, h) = (WINW as f32 / 2.0, WINH as f32 / 2.0);
+		let (w, h) = (size.width as f32 / 2.0, size.height as f32 / 2.0);
 
 		let square: &[Vert] = &[
 			Vert {
@@ -131,8 +131,8 @@ impl Renderer {
 			.create_shader_module(include_wgsl!("./shaders/main.wgsl"));
 
 		let texture_size = wgpu::Extent3d {
-			width: WINW as u32,
-			height: WINH as u32,
+			width: size.width,
+			height: size.height,
 			depth_or_array_layers: 1
 		};
 
@@ -148,8 +148,8 @@ impl Renderer {
 		let gui_texture = render_utils::Texture::new(
 			&rendering_core.device,
 			wgpu::Extent3d {
-				width: (WINW as f32 / (1.0 + GUI_PIXELATION)) as u32,
-				height: (WINH as f32 / (1.0 + GUI_PIXELATION)) as u32,
+				width: (size.width as f32 / (1.0 + GUI_PIXELATION)) as u32,
+				height: (size.height as f32 / (1.0 + GUI_PIXELATION)) as u32,
 				depth_or_array_layers: 1
 			},
 			rendering_core.surface_format, // TextureFormat::Rgba8UnormSrgb,
@@ -160,13 +160,23 @@ impl Renderer {
 			"GUI"
 		);
 
-		let proj_matrix: Matrix4<f32> = cgmath::ortho(-w, w, h, -h, -1.0, 1.0);
-		let model_matrix: Matrix4<f32> = Matrix4::from_nonuniform_scale(
-			XRES as f32 / WINW as f32,
-			YRES as f32 / WINH as f32,
-			1.0
+		// One pixel per wall cell
+		let wall_texture = render_utils::Texture::new(
+			&rendering_core.device,
+			wgpu::Extent3d {
+				width: size.width.div_ceil(CELL as u32),
+				height: size.height.div_ceil(CELL as u32),
+				depth_or_array_layers: 1
+			},
+			TextureFormat::Rgba8UnormSrgb,
+			TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST,
+			ShaderStages::FRAGMENT,
+			"Walls"
 		);
 
+		let proj_matrix: Matrix4<f32> = cgmath::ortho(-w, w, h, -h, -1.0, 1.0);
+		let model_matrix: Matrix4<f32> = Matrix4::identity();
+
 		let temp_val = Uniforms {
 			mat:     (proj_matrix * model_matrix * OPENGL_TO_WGPU_MATRIX).into(),
 			gui_mat: (proj_matrix * model_matrix * OPENGL_TO_WGPU_MATRIX).into(),
@@ -198,10 +208,12 @@ impl Renderer {
 			bindings:         vec![
 				screen_texture.bind_group.clone(),
 				gui_texture.bind_group.clone(),
+				wall_texture.bind_group.clone(),
 			],
 			bindings_layout:  vec![
 				screen_texture.bind_group_layout.clone(),
 				gui_texture.bind_group_layout.clone(),
+				wall_texture.bind_group_layout.clone(),
 			],
 			format:           rendering_core.surface_format
 		});
@@ -213,6 +225,8 @@ impl Renderer {
 
 				screen_texture,
 				gui_texture,
+				wall_texture,
+				wall_revision: None,
 
 				window_scale_factor: Vector2::new(1.0, 1.0),
 				camera_zoom: 1.0,
@@ -243,7 +257,8 @@ impl Renderer {
 
 		// Adjust size
 		let (ww, wh) = (core.window_size.width, core.window_size.height);
-		let mut window_size = Vector2::new(ww as f32 / WINW as f32, wh as f32 / WINH as f32);
+		let (bw, bh) = (core.base_size.width, core.base_size.height);
+		let mut window_size = Vector2::new(ww as f32 / bw as f32, wh as f32 / bh as f32);
 		window_size = window_size / window_size.x.min(window_size.y);
 		self.window_scale_factor = window_size;
 
@@ -275,24 +290,51 @@ impl Renderer {
 		);
 
 		// Generate texture
-		let mut tex_data = TextureData::new(XRES, YRES);
-		let mut counter = 0;
-		for i in 0..sim.parts.len() {
-			if counter >= sim.get_part_count() {
-				break;
-			}
+		let mut tex_data = TextureData::new(sim.get_width(), sim.get_height());
+		let mut energy = Vec::new();
+		for i in 0..sim.get_parts_end() {
 			let pt = sim.get_part(i);
 			if pt.p_type != 0 {
-				let col = pt.get_type(&sim.element_manager).col;
+				let el = pt.get_type(&sim.element_manager);
+				if matches!(el.behaviour, ElementBehaviour::Energy) {
+					energy.push(i);
+					continue;
+				}
+
+				let col = el.col;
 				tex_data.set_pixel(
 					pt.x as usize,
 					pt.y as usize,
 					(col[0], col[1], col[2], pt.p_type as u8)
 				);
-				counter += 1;
 			}
 		}
 
+		// Energy particles are added on top of whatever they overlap
+		for i in energy {
+			let pt = sim.get_part(i);
+			let col = pt.get_type(&sim.element_manager).col;
+			let (x, y) = (pt.x as usize, pt.y as usize);
+			let under = tex_data.get_pixel(x, y);
+			let p_type = if under.3 == 0 {
+				pt.p_type as u8
+			}
+			else {
+				under.3
+			};
+
+			tex_data.set_pixel(
+				x,
+				y,
+				(
+					under.0.saturating_add(col[0]),
+					under.1.saturating_add(col[1]),
+					under.2.saturating_add(col[2]),
+					p_type
+				)
+			);
+		}
+
 		self.draw_cursor(&mut tex_data, gui);
 
 		core.queue.write_texture(
@@ -311,6 +353,33 @@ impl Renderer {
 			self.screen_texture.size
 		);
 
+		let walls = sim.get_walls();
+		if self.wall_revision != Some(walls.get_revision()) {
+			self.wall_revision = Some(walls.get_revision());
+
+			let mut wall_data = TextureData::new(walls.get_width(), walls.get_height());
+			for i in 0..walls.get_width() * walls.get_height() {
+				let col = walls.get_cell_color(i);
+				wall_data.set_pixel(i % walls.get_width(), i / walls.get_width(), col);
+			}
+
+			core.queue.write_texture(
+				ImageCopyTexture {
+					texture:   &self.wall_texture.texture,
+					aspect:    TextureAspect::All,
+					origin:    Origin3d::ZERO,
+					mip_level: 0
+				},
+				wall_data.as_slice(),
+				ImageDataLayout {
+					offset:         0,
+					bytes_per_row:  Some(4 * self.wall_texture.size.width),
+					rows_per_image: Some(self.wall_texture.size.height)
+				},
+				self.wall_texture.size
+			);
+		}
+
 		// WGPU stuff This is a bit messy, well that's the price you pay not using unsafe rust :P
 		gui.gui_root.borrow().draw(&mut gui.immediate_gui);
 		drop(core);
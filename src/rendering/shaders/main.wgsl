@@ -44,6 +44,14 @@ var gui_text: texture_2d<f32>;
 @group(2) @binding(1)
 var gui_sampl: sampler;
 
+// One pixel per wall cell
+@group(3) @binding(0)
+var wall_text: texture_2d<f32>;
+@group(3) @binding(1)
+var wall_sampl: sampler;
+
+const WALL_CELL: f32 = 4.0; // Size of a wall cell in sim pixels
+
 fn lerp(a: vec4<f32>, b: vec4<f32>, t: f32) -> vec4<f32>
 {
     return ((b - a) * t) + a;
@@ -99,6 +107,12 @@ fn fs_main(
     var allOther: vec4<f32> = rgb_color * f32(ptype != 3 && ptype != 2);
 
     rgb_color = watrCol + dustCol + gridFactor + allOther;
+
+    // The wall grid can be a bit larger than the sim when its size isn't a multiple of the cell
+    var wall_coord: vec2<f32> = in.texture_coord * vec2<f32>(textureDimensions(text, 0)) /
+        (vec2<f32>(textureDimensions(wall_text, 0)) * WALL_CELL);
+    var wallCol: vec4<f32> = textureSample(wall_text, wall_sampl, wall_coord);
+    rgb_color = lerp(rgb_color, wallCol, wallCol.a * f32(ptype == 0));
     rgb_color.a = 1.0;//f32(ptype != 0);
 
     var real_coord = in.texture_coord;
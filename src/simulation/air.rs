@@ -0,0 +1,196 @@
+use crate::simulation::walls::Walls;
+
+pub const CELL: usize = 4; // Size of an air cell in sim pixels
+
+const MAX_PRESSURE: f32 = 256.0;
+const MIN_PRESSURE: f32 = -256.0;
+
+const PRESSURE_STEP: f32 = 0.3; // How much velocity divergence changes pressure
+const VELOCITY_STEP: f32 = 0.4; // How much pressure difference changes velocity
+const ADVECTION: f32 = 0.3; // How much of the velocity gets carried along with the air
+const PRESSURE_LOSS: f32 = 0.9999;
+const VELOCITY_LOSS: f32 = 0.999;
+const EDGE_LOSS: f32 = 0.8;
+
+/// Pressure and velocity of air on a grid of `CELL`x`CELL` sim pixels
+pub struct Air {
+	pub pressure: Box<[f32]>,
+	pub vx:       Box<[f32]>,
+	pub vy:       Box<[f32]>,
+
+	// Written to during update, then swapped with the fields above
+	next_pressure: Box<[f32]>,
+	next_vx:       Box<[f32]>,
+	next_vy:       Box<[f32]>,
+
+	kernel: [f32; 9],
+	width:  usize, // In cells
+	height: usize
+}
+
+impl Air {
+	/// Takes the size of the simulation in sim pixels
+	pub fn new(width: usize, height: usize) -> Self {
+		let (width, height) = (width.div_ceil(CELL), height.div_ceil(CELL));
+		let grid = vec![0f32; width * height].into_boxed_slice();
+
+		// Gaussian blur used for diffusion
+		let mut kernel = [0f32; 9];
+		for (i, k) in kernel.iter_mut().enumerate() {
+			let (dx, dy) = ((i % 3) as f32 - 1.0, (i / 3) as f32 - 1.0);
+			*k = (-2.0 * (dx * dx + dy * dy)).exp();
+		}
+		let sum: f32 = kernel.iter().sum();
+		kernel.iter_mut().for_each(|k| *k /= sum);
+
+		Self {
+			pressure: grid.clone(),
+			vx: grid.clone(),
+			vy: grid.clone(),
+			next_pressure: grid.clone(),
+			next_vx: grid.clone(),
+			next_vy: grid,
+			kernel,
+			width,
+			height
+		}
+	}
+
+	/// Index of the air cell containing sim position x, y
+	pub fn cell_index(&self, x: usize, y: usize) -> Option<usize> {
+		let (cx, cy) = (x / CELL, y / CELL);
+		if cx >= self.width || cy >= self.height {
+			return None;
+		}
+
+		Some(cx + cy * self.width)
+	}
+
+	pub fn velocity_at(&self, x: usize, y: usize) -> (f32, f32) {
+		match self.cell_index(x, y) {
+			Some(i) => (self.vx[i], self.vy[i]),
+			None => (0.0, 0.0)
+		}
+	}
+
+	pub fn pressure_at(&self, x: usize, y: usize) -> f32 {
+		match self.cell_index(x, y) {
+			Some(i) => self.pressure[i],
+			None => 0.0
+		}
+	}
+
+	pub fn add_pressure(&mut self, x: usize, y: usize, amount: f32) {
+		if let Some(i) = self.cell_index(x, y) {
+			self.pressure[i] = (self.pressure[i] + amount).clamp(MIN_PRESSURE, MAX_PRESSURE);
+		}
+	}
+
+	/// Walls that block air have no pressure and no air flows into or out of them
+	pub fn update(&mut self, walls: &Walls) {
+		let (width, height) = (self.width, self.height);
+		let blocked = |i: usize| walls.get_cell(i).blocks_air();
+
+		// Air flowing into a cell raises its pressure
+		for y in 1..height - 1 {
+			for x in 1..width - 1 {
+				let i = x + y * width;
+				if blocked(i) {
+					self.pressure[i] = 0.0;
+					continue;
+				}
+				let divergence = (self.vx[i - 1] - self.vx[i]) + (self.vy[i - width] - self.vy[i]);
+
+				self.pressure[i] = self.pressure[i] * PRESSURE_LOSS + divergence * PRESSURE_STEP;
+			}
+		}
+
+		// Pressure differences push the air
+		for y in 0..height - 1 {
+			for x in 0..width - 1 {
+				let i = x + y * width;
+				let dx = self.pressure[i] - self.pressure[i + 1];
+				let dy = self.pressure[i] - self.pressure[i + width];
+
+				self.vx[i] = self.vx[i] * VELOCITY_LOSS + dx * VELOCITY_STEP;
+				self.vy[i] = self.vy[i] * VELOCITY_LOSS + dy * VELOCITY_STEP;
+
+				// vx and vy are the flow into the right and bottom neighbour
+				if blocked(i) || blocked(i + 1) {
+					self.vx[i] = 0.0;
+				}
+				if blocked(i) || blocked(i + width) {
+					self.vy[i] = 0.0;
+				}
+			}
+		}
+
+		for y in 0..height {
+			for x in 0..width {
+				let i = x + y * width;
+
+				if blocked(i) {
+					self.next_pressure[i] = 0.0;
+					self.next_vx[i] = 0.0;
+					self.next_vy[i] = 0.0;
+					continue;
+				}
+
+				if x == 0 || y == 0 || x == width - 1 || y == height - 1 {
+					self.next_pressure[i] = self.pressure[i] * EDGE_LOSS;
+					self.next_vx[i] = 0.0;
+					self.next_vy[i] = 0.0;
+					continue;
+				}
+
+				// Diffusion, walls reflect the cell's own air back
+				let (mut pressure, mut vx, mut vy) = (0.0, 0.0, 0.0);
+				for (k, weight) in self.kernel.iter().enumerate() {
+					let j = (x + k % 3 - 1) + (y + k / 3 - 1) * width;
+					let j = if blocked(j) { i } else { j };
+					pressure += self.pressure[j] * weight;
+					vx += self.vx[j] * weight;
+					vy += self.vy[j] * weight;
+				}
+
+				// Advection, velocity is carried over from where the air came from
+				let (tx, ty) = (x as f32 - vx, y as f32 - vy);
+				if tx >= 0.0 && ty >= 0.0 && tx < (width - 1) as f32 && ty < (height - 1) as f32 {
+					let (carried_vx, carried_vy) = self.sample_velocity(tx, ty);
+					vx = vx * (1.0 - ADVECTION) + carried_vx * ADVECTION;
+					vy = vy * (1.0 - ADVECTION) + carried_vy * ADVECTION;
+				}
+
+				if blocked(i + 1) {
+					vx = 0.0;
+				}
+				if blocked(i + width) {
+					vy = 0.0;
+				}
+
+				self.next_pressure[i] = pressure.clamp(MIN_PRESSURE, MAX_PRESSURE);
+				self.next_vx[i] = vx;
+				self.next_vy[i] = vy;
+			}
+		}
+
+		std::mem::swap(&mut self.pressure, &mut self.next_pressure);
+		std::mem::swap(&mut self.vx, &mut self.next_vx);
+		std::mem::swap(&mut self.vy, &mut self.next_vy);
+	}
+
+	// Bilinear interpolation, x and y are in cell coordinates
+	fn sample_velocity(&self, x: f32, y: f32) -> (f32, f32) {
+		let (cx, cy) = (x as usize, y as usize);
+		let (fx, fy) = (x - cx as f32, y - cy as f32);
+		let i = cx + cy * self.width;
+
+		let lerp = |field: &[f32]| {
+			let top = field[i] * (1.0 - fx) + field[i + 1] * fx;
+			let bottom = field[i + self.width] * (1.0 - fx) + field[i + self.width + 1] * fx;
+			top * (1.0 - fy) + bottom * fy
+		};
+
+		(lerp(&self.vx), lerp(&self.vy))
+	}
+}
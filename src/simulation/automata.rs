@@ -0,0 +1,179 @@
+use std::fmt;
+
+use crate::simulation::elements::AUTOMATON;
+use crate::simulation::properties::{PropKind, PropSlot, Property};
+use crate::simulation::sim::{ParticleHandle, Simulation, DIRECTIONS};
+use crate::simulation::Particle;
+
+// Life cells keep their rule in prop1 and prop2, so cells of different rules can share a board
+pub const BIRTH: Property = Property {
+	name: "birth",
+	slot: PropSlot::Prop1,
+	kind: PropKind::Int
+};
+pub const SURVIVAL: Property = Property {
+	name: "survival",
+	slot: PropSlot::Prop2,
+	kind: PropKind::Int
+};
+
+/// Neighbour counts at which cells are born and survive, bit n stands for n neighbours
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LifeRule {
+	pub birth:    u16,
+	pub survival: u16
+}
+
+impl LifeRule {
+	/// Parses rules like "B3/S23", returns None if the rule is invalid
+	pub const fn parse(rule: &str) -> Option<Self> {
+		let bytes = rule.as_bytes();
+		let (mut birth, mut survival) = (0u16, 0u16);
+		let mut i = 0;
+
+		if bytes.len() < 3 || (bytes[0] != b'B' && bytes[0] != b'b') {
+			return None;
+		}
+		i += 1;
+		while i < bytes.len() && bytes[i] != b'/' {
+			match bytes[i] {
+				b'0'..=b'8' => birth |= 1 << (bytes[i] - b'0'),
+				_ => return None
+			}
+			i += 1;
+		}
+
+		if i + 1 >= bytes.len() || (bytes[i + 1] != b'S' && bytes[i + 1] != b's') {
+			return None;
+		}
+		i += 2;
+		while i < bytes.len() {
+			match bytes[i] {
+				b'0'..=b'8' => survival |= 1 << (bytes[i] - b'0'),
+				_ => return None
+			}
+			i += 1;
+		}
+
+		Some(Self { birth, survival })
+	}
+
+	pub fn born(self, neighbours: usize) -> bool {
+		self.birth & (1 << neighbours) != 0
+	}
+
+	pub fn survives(self, neighbours: usize) -> bool {
+		self.survival & (1 << neighbours) != 0
+	}
+
+	pub fn get(pt: &Particle) -> Self {
+		Self {
+			birth:    BIRTH.get(pt),
+			survival: SURVIVAL.get(pt)
+		}
+	}
+
+	pub fn set(self, pt: &mut Particle) {
+		BIRTH.set(pt, self.birth);
+		SURVIVAL.set(pt, self.survival);
+	}
+}
+
+impl fmt::Display for LifeRule {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let digits = |mask: u16| {
+			(0..=8)
+				.filter(|n| mask & (1 << n) != 0)
+				.map(|n| n.to_string())
+				.collect::<String>()
+		};
+		write!(f, "B{}/S{}", digits(self.birth), digits(self.survival))
+	}
+}
+
+/// Default particle of a Life element, invalid rules fail to compile
+pub const fn life_cell(p_type: u16, rule: &str) -> Particle {
+	let Some(rule) = LifeRule::parse(rule)
+	else {
+		panic!("Invalid Life rule");
+	};
+
+	let mut pt = Particle::default().with_type(p_type);
+	pt.prop1 = rule.birth;
+	pt.prop2 = rule.survival;
+	pt
+}
+
+impl Simulation {
+	/// Advances all Life cells by one generation
+	/// Every cell sees the board as it was before the pass, only neighbours with the same rule count
+	pub(super) fn update_life(&mut self) {
+		let width = self.get_width();
+		let mut deaths: Vec<ParticleHandle> = Vec::new();
+		// Empty pixel next to a cell, the cell's rule and element
+		let mut births: Vec<(usize, LifeRule, u16)> = Vec::new();
+
+		for id in 0..self.get_parts_end() {
+			if !self.is_life(id) {
+				continue;
+			}
+			let pt = &self.parts[id];
+			let rule = LifeRule::get(pt);
+			let (x, y) = (pt.x as isize, pt.y as isize);
+
+			let mut neighbours = 0;
+			for (dx, dy) in DIRECTIONS {
+				let Some((nx, ny)) = self.wrap_pos(x + dx, y + dy)
+				else {
+					continue;
+				};
+
+				match self.get_id(nx, ny) {
+					Some(other) if other == id => {}
+					Some(other) => {
+						if self.is_life(other) && LifeRule::get(&self.parts[other]) == rule {
+							neighbours += 1;
+						}
+					}
+					None => births.push((nx + ny * width, rule, pt.p_type))
+				}
+			}
+
+			if !rule.survives(neighbours) {
+				deaths.push(id);
+			}
+		}
+
+		// Stable, so the cell with the lowest id decides the element of a new cell
+		births.sort_by_key(|(pos, rule, _)| (*pos, *rule));
+
+		for id in deaths {
+			self.region()
+				.kill_part(id)
+				.expect("Tried to kill invalid part");
+		}
+
+		let mut start = 0;
+		while start < births.len() {
+			let (pos, rule, p_type) = births[start];
+			let count = births[start..]
+				.iter()
+				.take_while(|(p, r, _)| *p == pos && *r == rule)
+				.count();
+			start += count;
+
+			// Pixels where several rules are born go to the first one
+			if !rule.born(count) || self.pmap[pos].is_some() {
+				continue;
+			}
+			if let Some(id) = self.create_part(p_type, pos % width, pos / width) {
+				rule.set(&mut self.parts[id]);
+			}
+		}
+	}
+
+	fn is_life(&self, id: ParticleHandle) -> bool {
+		let pt = &self.parts[id];
+		pt.p_type != 0 && pt.get_type(&self.element_manager).has_flag(AUTOMATON)
+	}
+}
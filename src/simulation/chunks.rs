@@ -0,0 +1,85 @@
+use crate::simulation::explosives::Blast;
+use crate::simulation::rng::SimRng;
+use crate::simulation::sim::ParticleHandle;
+
+/// Height of a chunk in sim pixels
+/// Chunks two apart are updated at the same time, so each one can only reach `REACH` rows past its own
+pub const CHUNK_HEIGHT: usize = 64;
+pub const REACH: usize = CHUNK_HEIGHT / 4;
+
+/// Horizontal strip of the board, only one thread updates it at a time
+/// Everything here is reset every tick, so the result doesn't depend on which thread ran the chunk
+pub(super) struct Chunk {
+	pub(super) parts:         Vec<ParticleHandle>, // Particles in the strip at tick start
+	pub(super) rng:           SimRng,
+	pub(super) freed:         Vec<ParticleHandle>, // Returned to the free list once the tick ends
+	pub(super) created:       usize,
+	pub(super) parts_end:     usize,
+	pub(super) blasts:        Vec<Blast>, // Applied by the simulation once all chunks are done
+	pub(super) sparked_walls: Vec<(usize, usize)>, // Same for conductor walls, by sim position
+
+	// Chunks take turns taking ids from the end of the free list, this one uses every `stride`th
+	index:        usize,
+	stride:       usize,
+	reserve_used: usize
+}
+
+impl Chunk {
+	pub(super) fn new(index: usize, stride: usize) -> Self {
+		Self {
+			parts: Vec::new(),
+			rng: SimRng::new(0),
+			freed: Vec::new(),
+			created: 0,
+			parts_end: 0,
+			blasts: Vec::new(),
+			sparked_walls: Vec::new(),
+			index,
+			stride,
+			reserve_used: 0
+		}
+	}
+
+	pub(super) fn reset(&mut self, rng: SimRng) {
+		self.parts.clear();
+		self.rng = rng;
+		self.freed.clear();
+		self.created = 0;
+		self.parts_end = 0;
+		self.blasts.clear();
+		self.sparked_walls.clear();
+		self.reserve_used = 0;
+	}
+
+	/// Takes the next id from this chunk's part of the free list
+	/// Ids freed during the tick aren't reused, another chunk might still have them in its list
+	pub(super) fn alloc(&mut self, free_list: &[ParticleHandle]) -> Option<ParticleHandle> {
+		let pos = self.index + self.reserve_used * self.stride;
+		if pos >= free_list.len() {
+			return None;
+		}
+
+		let id = free_list[free_list.len() - 1 - pos];
+		self.reserve_used += 1;
+		self.created += 1;
+		self.parts_end = self.parts_end.max(id + 1);
+		Some(id)
+	}
+
+	/// Removes ids taken by chunks this tick from the free list
+	pub(super) fn remove_taken(chunks: &[Chunk], free_list: &mut Vec<ParticleHandle>) {
+		let taken = chunks.iter().map(|c| c.reserve_used).max().unwrap_or(0);
+		let len = free_list.len();
+		let start = len.saturating_sub(taken * chunks.len());
+
+		let mut write = start;
+		for read in start..len {
+			let pos = len - 1 - read;
+			if pos / chunks.len() >= chunks[pos % chunks.len()].reserve_used {
+				free_list[write] = free_list[read];
+				write += 1;
+			}
+		}
+		free_list.truncate(write);
+	}
+}
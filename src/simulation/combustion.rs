@@ -0,0 +1,129 @@
+use std::ops::Range;
+
+use rand::Rng;
+
+use crate::simulation::elements::*;
+use crate::simulation::heat::{MAX_TEMP, MIN_TEMP};
+use crate::simulation::properties::{CTYPE, LIFE};
+use crate::simulation::region::Region;
+use crate::simulation::sim::ParticleHandle;
+
+pub const IGNITION_TEMP: f32 = 573.15; // Kelvin, flammable particles hotter than this catch fire on their own
+
+const FIRE_LIFE: Range<u16> = 120..170; // Ticks
+const PLASMA_LIFE: Range<u16> = 50..70;
+const SMOKE_CHANCE: f64 = 0.02; // Per tick of burning
+const HEATING: f32 = 0.05; // Part of the difference to the flame's temperature neighbours gain per tick
+
+// Fire and plasma use ctype for the element that's burning and life for ticks left
+// Ones made with life 0, for example with the brush, get a random life on their first update
+impl Region<'_> {
+	/// Burns fire and plasma and sets hot flammable particles on fire
+	/// Returns true if the particle was killed
+	pub(super) fn update_combustion(&mut self, pt_id: ParticleHandle) -> bool {
+		let pt = &self.parts[pt_id];
+		let life = match pt.p_type {
+			id if id == EL_FIRE.id => FIRE_LIFE,
+			id if id == EL_PLSM.id => PLASMA_LIFE,
+			_ => {
+				let flammability = pt.get_type(self.element_manager).flammability;
+				if flammability > 0.0 &&
+					pt.temp > IGNITION_TEMP &&
+					self.rng().gen_bool(flammability.min(1.0) as f64)
+				{
+					self.set_on_fire(pt_id);
+				}
+				return false;
+			}
+		};
+
+		self.burn(pt_id, life)
+	}
+
+	// Returns true if the particle was killed
+	fn burn(&mut self, pt_id: ParticleHandle, life: Range<u16>) -> bool {
+		let pt = &self.parts[pt_id];
+		let left = LIFE.get(pt);
+		if left == 1 {
+			return self.burn_out(pt_id);
+		}
+
+		let left = if left == 0 {
+			self.rng().gen_range(life)
+		}
+		else {
+			left - 1
+		};
+		let pt = &mut self.parts[pt_id];
+		LIFE.set(pt, left);
+
+		// Flames stay at least as hot as their element's default temperature
+		let flame_temp = pt.get_type(self.element_manager).default.temp;
+		pt.temp = pt.temp.max(flame_temp);
+
+		self.spread_fire(pt_id, flame_temp);
+
+		if self.parts[pt_id].p_type == EL_FIRE.id && self.rng().gen_bool(SMOKE_CHANCE) {
+			let (dx, dy) = (self.rng().gen_range(-1..=1), self.rng().gen_range(-1..=1));
+			let pt = &self.parts[pt_id];
+			if let Some((x, y)) = self.wrap_pos(pt.x as isize + dx, pt.y as isize + dy) {
+				self.create_part(EL_SMKE.id, x, y);
+			}
+		}
+		false
+	}
+
+	/// Heats the neighbours and sets flammable ones on fire
+	fn spread_fire(&mut self, pt_id: ParticleHandle, flame_temp: f32) {
+		let pt = &self.parts[pt_id];
+		let (x, y) = (pt.x as isize, pt.y as isize);
+
+		for dy in -1..=1 {
+			for dx in -1..=1 {
+				let Some(id) = self
+					.get_id_wrapped(x + dx, y + dy)
+					.filter(|&id| id != pt_id)
+				else {
+					continue;
+				};
+
+				let other = &mut self.parts[id];
+				let temp = other.temp + (flame_temp - other.temp) * HEATING;
+				other.temp = temp.clamp(MIN_TEMP, MAX_TEMP);
+
+				let flammability = other.get_type(self.element_manager).flammability;
+				if flammability > 0.0 && self.rng().gen_bool(flammability.min(1.0) as f64) {
+					self.set_on_fire(id);
+				}
+			}
+		}
+	}
+
+	fn set_on_fire(&mut self, id: ParticleHandle) {
+		let pt = &mut self.parts[id];
+		CTYPE.set(pt, pt.p_type);
+		LIFE.set(pt, 0);
+		pt.p_type = EL_FIRE.id;
+	}
+
+	/// Turns the particle into what the burning element leaves behind
+	/// Returns true if nothing is left
+	fn burn_out(&mut self, pt_id: ParticleHandle) -> bool {
+		let burning = CTYPE.get(&self.parts[pt_id]);
+		let product = self
+			.element_manager
+			.elements
+			.get(burning as usize)
+			.map_or(0, |el| el.burns_into);
+		if self.change_type(pt_id, product) {
+			return true;
+		}
+
+		let default = &self.element_manager.elements[product as usize].default;
+		let pt = &mut self.parts[pt_id];
+		pt.prop1 = default.prop1;
+		pt.prop2 = default.prop2;
+		pt.prop3 = default.prop3;
+		false
+	}
+}
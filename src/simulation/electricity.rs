@@ -0,0 +1,184 @@
+use crate::simulation::air::CELL;
+use crate::simulation::elements::*;
+use crate::simulation::properties::{CTYPE, LIFE, TMP};
+use crate::simulation::region::Region;
+use crate::simulation::sim::{ParticleHandle, Simulation};
+
+pub const SPARK_LIFE: u16 = 4; // Ticks a spark lasts
+pub const COOLDOWN: u16 = 4; // Ticks before a conductor can be sparked again, so sparks don't flow back
+
+const REACH: isize = 2; // Sparks jump up to this many pixels
+const SEMICONDUCTOR_TEMP: f32 = 373.15; // Kelvin
+
+// Sparks use ctype for the element they turn back into, life for ticks left
+// and tmp for the tick they were made on, they don't spread on that tick
+// Conductors use life for ticks left of the cooldown
+impl Simulation {
+	/// Turns the conductor at x, y into a spark, sets off an explosive or sparks a conductor wall
+	/// Returns false if there's nothing that can be sparked right now
+	pub fn spark(&mut self, x: usize, y: usize) -> bool {
+		self.region().spark(x, y)
+	}
+
+	/// Sparks conductors touching conductor walls that got sparked during the last tick
+	pub(super) fn update_wall_sparks(&mut self) {
+		let width = self.walls.get_width();
+		let cells = self.walls.update_sparks();
+		let mut region = self.region();
+
+		for cell in cells {
+			let (left, top) = (
+				(cell % width * CELL) as isize,
+				(cell / width * CELL) as isize
+			);
+			let size = CELL as isize;
+
+			// Pixels of the cell and the ones around it
+			for y in top - 1..=top + size {
+				for x in left - 1..=left + size {
+					if let Some(id) = region.get_id_wrapped(x, y) {
+						if region.can_spark(id) {
+							region.make_spark(id);
+						}
+					}
+				}
+			}
+		}
+	}
+}
+
+impl Region<'_> {
+	/// Returns false out of reach too
+	pub(super) fn spark(&mut self, x: usize, y: usize) -> bool {
+		if !self.in_reach(x, y) {
+			return false;
+		}
+
+		match self.get_id(x, y) {
+			Some(id) if self.can_spark(id) => {
+				self.make_spark(id);
+				true
+			}
+			Some(id) => self.detonate(id),
+			None => self.spark_wall(x, y)
+		}
+	}
+
+	pub(super) fn can_spark(&self, id: ParticleHandle) -> bool {
+		let pt = &self.parts[id];
+		let el = pt.get_type(self.element_manager);
+
+		el.has_flag(CONDUCTIVE) &&
+			LIFE.get(pt) == 0 &&
+			(!el.has_flag(SEMICONDUCTOR) || pt.temp > SEMICONDUCTOR_TEMP)
+	}
+
+	pub(super) fn make_spark(&mut self, id: ParticleHandle) {
+		let tick = self.get_tick() as u16;
+		let pt = &mut self.parts[id];
+
+		CTYPE.set(pt, pt.p_type);
+		pt.p_type = EL_SPRK.id;
+		LIFE.set(pt, SPARK_LIFE);
+		TMP.set(pt, tick);
+	}
+
+	/// Moves sparks along, counts down conductor cooldowns and lets spark sources spark
+	/// Returns true if the particle was killed
+	pub(super) fn update_electricity(&mut self, pt_id: ParticleHandle) -> bool {
+		let pt = &self.parts[pt_id];
+		if pt.p_type == EL_SPRK.id {
+			return self.update_spark(pt_id);
+		}
+
+		let el = pt.get_type(self.element_manager);
+		let cooldown = if el.has_flag(CONDUCTIVE) {
+			LIFE.get(pt)
+		}
+		else {
+			0
+		};
+		if el.has_flag(SPARK_SOURCE) {
+			self.spread_spark(pt_id);
+		}
+		if cooldown > 0 {
+			LIFE.set(&mut self.parts[pt_id], cooldown - 1);
+		}
+		false
+	}
+
+	// Returns true if the particle was killed
+	fn update_spark(&mut self, pt_id: ParticleHandle) -> bool {
+		let pt = &self.parts[pt_id];
+
+		// Sparks move one pixel per tick no matter in which order particles are updated
+		if TMP.get(pt) == self.get_tick() as u16 {
+			return false;
+		}
+		let life = LIFE.get(pt);
+		if life == SPARK_LIFE {
+			self.spread_spark(pt_id);
+		}
+
+		let pt = &mut self.parts[pt_id];
+		LIFE.set(pt, life.saturating_sub(1));
+		if life > 1 {
+			return false;
+		}
+
+		let conductor = CTYPE.get(pt);
+		if self.change_type(pt_id, conductor) {
+			return true;
+		}
+
+		let pt = &mut self.parts[pt_id];
+		CTYPE.set(pt, 0);
+		LIFE.set(pt, COOLDOWN);
+		TMP.set(pt, 0);
+		false
+	}
+
+	/// Sparks conductors and conductor walls around the particle and sets off explosives
+	fn spread_spark(&mut self, pt_id: ParticleHandle) {
+		let pt = &self.parts[pt_id];
+		let (x, y) = (pt.x as isize, pt.y as isize);
+
+		for dy in -REACH..=REACH {
+			for dx in -REACH..=REACH {
+				if self.insulated(x, y, dx, dy) {
+					continue;
+				}
+				let Some((nx, ny)) = self.wrap_pos(x + dx, y + dy)
+				else {
+					continue;
+				};
+
+				match self.get_id(nx, ny) {
+					Some(id) if id == pt_id => {}
+					Some(id) if self.can_spark(id) => self.make_spark(id),
+					Some(id) => {
+						self.detonate(id);
+					}
+					None if self.in_reach(nx, ny) => {
+						self.spark_wall(nx, ny);
+					}
+					None => {}
+				}
+			}
+		}
+	}
+
+	// Sparks can't jump over an insulator
+	fn insulated(&self, x: isize, y: isize, dx: isize, dy: isize) -> bool {
+		if dx.abs() < 2 && dy.abs() < 2 {
+			return false;
+		}
+
+		let Some(id) = self.get_id_wrapped(x + dx / 2, y + dy / 2)
+		else {
+			return false;
+		};
+		let el = self.parts[id].get_type(self.element_manager);
+		el.has_flag(INSULATOR)
+	}
+}
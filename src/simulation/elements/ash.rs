@@ -0,0 +1,35 @@
+use crate::simulation::elements::Element;
+use crate::simulation::elements::ElementBehaviour::Powder;
+use crate::simulation::Particle;
+
+// Left by burnt wood
+pub const ID: u16 = 17;
+pub const EL_ASH: Element = Element {
+	id:            ID,
+	name:          "ASH",
+	col:           [144, 136, 128, 255],
+	behaviour:     Powder,
+	density:       6,
+	spread:        0,
+	diffusion:     0.0,
+	heat_capacity: 0.8,
+	conductivity:  0.1,
+	advection:     0.3,
+	loss:          0.8,
+	bounce:        0.0,
+	friction:      0.8,
+	gravity:       0.5,
+	max_velocity:  2.0,
+	mass:          0.0,
+	flammability:  0.0,
+	burns_into:    0,
+	explosion:     None,
+	flags:         0,
+	properties:    &[],
+	high_temp:     None,
+	low_temp:      None,
+	high_pressure: None,
+	low_pressure:  None,
+	update:        None,
+	default:       Particle::default().with_type(ID)
+};
@@ -1,14 +1,34 @@
-use crate::simulation::elements::Element;
 use crate::simulation::elements::ElementBehaviour::Solid;
+use crate::simulation::elements::{Element, NEUTRON_ABSORBER};
 use crate::simulation::Particle;
 
-const ID: u16 = 1u16;
+pub const ID: u16 = 1u16;
 pub const EL_BRCK: Element = Element {
-	id:        ID,
-	name:      "BRCK",
-	col:       [128, 128, 128, 255],
-	behaviour: Solid,
-	density:   20,
-	update:    None,
-	default:   Particle::default().with_type(ID)
+	id:            ID,
+	name:          "BRCK",
+	col:           [128, 128, 128, 255],
+	behaviour:     Solid,
+	density:       20,
+	spread:        0,
+	diffusion:     0.0,
+	heat_capacity: 0.84,
+	conductivity:  0.98,
+	advection:     0.0,
+	loss:          0.0,
+	bounce:        0.0,
+	friction:      0.0,
+	gravity:       0.0,
+	max_velocity:  0.0,
+	mass:          0.0,
+	flammability:  0.0,
+	burns_into:    0,
+	explosion:     None,
+	flags:         NEUTRON_ABSORBER,
+	properties:    &[],
+	high_temp:     None,
+	low_temp:      None,
+	high_pressure: None,
+	low_pressure:  None,
+	update:        None,
+	default:       Particle::default().with_type(ID)
 };
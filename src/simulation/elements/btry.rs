@@ -0,0 +1,35 @@
+use crate::simulation::elements::ElementBehaviour::Solid;
+use crate::simulation::elements::{Element, SPARK_SOURCE};
+use crate::simulation::Particle;
+
+// Battery, keeps sparking conductors next to it
+pub const ID: u16 = 11;
+pub const EL_BTRY: Element = Element {
+	id:            ID,
+	name:          "BTRY",
+	col:           [133, 133, 90, 255],
+	behaviour:     Solid,
+	density:       100,
+	spread:        0,
+	diffusion:     0.0,
+	heat_capacity: 1.0,
+	conductivity:  0.1,
+	advection:     0.0,
+	loss:          0.0,
+	bounce:        0.0,
+	friction:      0.0,
+	gravity:       0.0,
+	max_velocity:  0.0,
+	mass:          0.0,
+	flammability:  0.0,
+	burns_into:    0,
+	explosion:     None,
+	flags:         SPARK_SOURCE,
+	properties:    &[],
+	high_temp:     None,
+	low_temp:      None,
+	high_pressure: None,
+	low_pressure:  None,
+	update:        None,
+	default:       Particle::default().with_type(ID)
+};
@@ -0,0 +1,39 @@
+use crate::simulation::elements::ElementBehaviour::Solid;
+use crate::simulation::elements::{Element, Explosion};
+use crate::simulation::Particle;
+
+// High yield explosive, breaks solids in a wide radius
+pub const ID: u16 = 21;
+pub const EL_C4: Element = Element {
+	id:            ID,
+	name:          "C4",
+	col:           [208, 128, 224, 255],
+	behaviour:     Solid,
+	density:       100,
+	spread:        0,
+	diffusion:     0.0,
+	heat_capacity: 1.0,
+	conductivity:  0.1,
+	advection:     0.0,
+	loss:          0.0,
+	bounce:        0.0,
+	friction:      0.0,
+	gravity:       0.0,
+	max_velocity:  0.0,
+	mass:          0.0,
+	flammability:  0.0,
+	burns_into:    0,
+	explosion:     Some(Explosion {
+		radius: 16.0,
+		force:  10.0,
+		heat:   2500.0
+	}),
+	flags:         0,
+	properties:    &[],
+	high_temp:     None,
+	low_temp:      None,
+	high_pressure: None,
+	low_pressure:  None,
+	update:        None,
+	default:       Particle::default().with_type(ID)
+};
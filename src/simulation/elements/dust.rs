@@ -2,13 +2,33 @@ use crate::simulation::elements::Element;
 use crate::simulation::elements::ElementBehaviour::Powder;
 use crate::simulation::Particle;
 
-const ID: u16 = 2;
+pub const ID: u16 = 2;
 pub const EL_DUST: Element = Element {
-	id:        ID,
-	name:      "DUST",
-	col:       [220, 220, 0, 255],
-	behaviour: Powder,
-	density:   10,
-	update:    None,
-	default:   Particle::default().with_type(ID)
+	id:            ID,
+	name:          "DUST",
+	col:           [220, 220, 0, 255],
+	behaviour:     Powder,
+	density:       10,
+	spread:        0,
+	diffusion:     0.0,
+	heat_capacity: 1.0,
+	conductivity:  0.27,
+	advection:     0.1,
+	loss:          0.95,
+	bounce:        0.1,
+	friction:      0.6,
+	gravity:       1.0,
+	max_velocity:  10.0,
+	mass:          0.0,
+	flammability:  0.1,
+	burns_into:    0,
+	explosion:     None,
+	flags:         0,
+	properties:    &[],
+	high_temp:     None,
+	low_temp:      None,
+	high_pressure: None,
+	low_pressure:  None,
+	update:        None,
+	default:       Particle::default().with_type(ID)
 };
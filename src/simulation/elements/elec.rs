@@ -0,0 +1,36 @@
+use crate::simulation::elements::Element;
+use crate::simulation::elements::ElementBehaviour::Energy;
+use crate::simulation::properties::LIFE;
+use crate::simulation::Particle;
+
+// Sparks the conductors it hits, stopped by everything
+pub const ID: u16 = 27;
+pub const EL_ELEC: Element = Element {
+	id:            ID,
+	name:          "ELEC",
+	col:           [223, 239, 255, 255],
+	behaviour:     Energy,
+	density:       0,
+	spread:        0,
+	diffusion:     0.0,
+	heat_capacity: 0.0,
+	conductivity:  0.0,
+	advection:     0.0,
+	loss:          0.0,
+	bounce:        0.0,
+	friction:      0.0,
+	gravity:       0.0,
+	max_velocity:  0.0,
+	mass:          0.0,
+	flammability:  0.0,
+	burns_into:    0,
+	explosion:     None,
+	flags:         0,
+	properties:    &[LIFE],
+	high_temp:     None,
+	low_temp:      None,
+	high_pressure: None,
+	low_pressure:  None,
+	update:        None,
+	default:       Particle::default().with_type(ID)
+};
@@ -0,0 +1,36 @@
+use crate::simulation::elements::Element;
+use crate::simulation::elements::ElementBehaviour::Gas;
+use crate::simulation::properties::{CTYPE, LIFE};
+use crate::simulation::Particle;
+
+// Spreads to flammable neighbours and burns out into what the burning element leaves behind
+pub const ID: u16 = 14;
+pub const EL_FIRE: Element = Element {
+	id:            ID,
+	name:          "FIRE",
+	col:           [255, 96, 16, 255],
+	behaviour:     Gas,
+	density:       1,
+	spread:        0,
+	diffusion:     0.3,
+	heat_capacity: 0.5,
+	conductivity:  0.88,
+	advection:     0.3,
+	loss:          0.9,
+	bounce:        0.0,
+	friction:      1.0,
+	gravity:       -0.5,
+	max_velocity:  4.0,
+	mass:          0.0,
+	flammability:  0.0,
+	burns_into:    0,
+	explosion:     None,
+	flags:         0,
+	properties:    &[CTYPE, LIFE],
+	high_temp:     None,
+	low_temp:      None,
+	high_pressure: None,
+	low_pressure:  None,
+	update:        None,
+	default:       Particle::default().with_type(ID).with_temp(695.15)
+};
@@ -0,0 +1,39 @@
+use crate::simulation::elements::ElementBehaviour::Powder;
+use crate::simulation::elements::{Element, Explosion};
+use crate::simulation::Particle;
+
+// Explodes in small blasts that set off the rest of the pile
+pub const ID: u16 = 20;
+pub const EL_GUNP: Element = Element {
+	id:            ID,
+	name:          "GUNP",
+	col:           [192, 192, 208, 255],
+	behaviour:     Powder,
+	density:       10,
+	spread:        0,
+	diffusion:     0.0,
+	heat_capacity: 1.0,
+	conductivity:  0.2,
+	advection:     0.1,
+	loss:          0.95,
+	bounce:        0.1,
+	friction:      0.6,
+	gravity:       1.0,
+	max_velocity:  10.0,
+	mass:          0.0,
+	flammability:  0.0,
+	burns_into:    0,
+	explosion:     Some(Explosion {
+		radius: 4.0,
+		force:  3.0,
+		heat:   400.0
+	}),
+	flags:         0,
+	properties:    &[],
+	high_temp:     None,
+	low_temp:      None,
+	high_pressure: None,
+	low_pressure:  None,
+	update:        None,
+	default:       Particle::default().with_type(ID)
+};
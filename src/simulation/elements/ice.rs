@@ -0,0 +1,37 @@
+use crate::simulation::elements::ElementBehaviour::Solid;
+use crate::simulation::elements::{watr, Element, Transition, TRANSPARENT};
+use crate::simulation::Particle;
+
+pub const ID: u16 = 4;
+pub const EL_ICE: Element = Element {
+	id:            ID,
+	name:          "ICE",
+	col:           [160, 192, 255, 255],
+	behaviour:     Solid,
+	density:       20,
+	spread:        0,
+	diffusion:     0.0,
+	heat_capacity: 2.1,
+	conductivity:  0.18,
+	advection:     0.0,
+	loss:          0.0,
+	bounce:        0.0,
+	friction:      0.0,
+	gravity:       0.0,
+	max_velocity:  0.0,
+	mass:          0.0,
+	flammability:  0.0,
+	burns_into:    0,
+	explosion:     None,
+	flags:         TRANSPARENT,
+	properties:    &[],
+	high_temp:     Some(Transition {
+		threshold: 274.15,
+		into:      watr::ID
+	}),
+	low_temp:      None,
+	high_pressure: None,
+	low_pressure:  None,
+	update:        None,
+	default:       Particle::default().with_type(ID).with_temp(263.15)
+};
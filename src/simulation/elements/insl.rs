@@ -0,0 +1,35 @@
+use crate::simulation::elements::ElementBehaviour::Solid;
+use crate::simulation::elements::{Element, INSULATOR};
+use crate::simulation::Particle;
+
+// Insulator, sparks can't jump over it
+pub const ID: u16 = 12;
+pub const EL_INSL: Element = Element {
+	id:            ID,
+	name:          "INSL",
+	col:           [158, 158, 158, 255],
+	behaviour:     Solid,
+	density:       100,
+	spread:        0,
+	diffusion:     0.0,
+	heat_capacity: 1.0,
+	conductivity:  0.0,
+	advection:     0.0,
+	loss:          0.0,
+	bounce:        0.0,
+	friction:      0.0,
+	gravity:       0.0,
+	max_velocity:  0.0,
+	mass:          0.0,
+	flammability:  0.0,
+	burns_into:    0,
+	explosion:     None,
+	flags:         INSULATOR,
+	properties:    &[],
+	high_temp:     None,
+	low_temp:      None,
+	high_pressure: None,
+	low_pressure:  None,
+	update:        None,
+	default:       Particle::default().with_type(ID)
+};
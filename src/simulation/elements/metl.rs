@@ -0,0 +1,35 @@
+use crate::simulation::elements::ElementBehaviour::Solid;
+use crate::simulation::elements::{Element, CONDUCTIVE};
+use crate::simulation::properties::LIFE;
+use crate::simulation::Particle;
+
+pub const ID: u16 = 9;
+pub const EL_METL: Element = Element {
+	id:            ID,
+	name:          "METL",
+	col:           [112, 112, 160, 255],
+	behaviour:     Solid,
+	density:       100,
+	spread:        0,
+	diffusion:     0.0,
+	heat_capacity: 0.45,
+	conductivity:  0.96,
+	advection:     0.0,
+	loss:          0.0,
+	bounce:        0.0,
+	friction:      0.0,
+	gravity:       0.0,
+	max_velocity:  0.0,
+	mass:          0.0,
+	flammability:  0.0,
+	burns_into:    0,
+	explosion:     None,
+	flags:         CONDUCTIVE,
+	properties:    &[LIFE],
+	high_temp:     None,
+	low_temp:      None,
+	high_pressure: None,
+	low_pressure:  None,
+	update:        None,
+	default:       Particle::default().with_type(ID)
+};
@@ -2,7 +2,9 @@ use proc_macros::import_elements;
 
 import_elements!("src/simulation/elements");
 
-use crate::simulation::Particle;
+use crate::simulation::properties::Property;
+use crate::simulation::reactions::{Reaction, REACTIONS};
+use crate::simulation::{Particle, UpdateContext};
 
 // TODO: Separate stuff
 
@@ -12,32 +14,179 @@ pub enum ElementBehaviour {
 	Solid,
 	Powder,
 	Fluid,
-	Gas
+	Gas,
+	Energy // Overlaps matter and moves in straight lines, see `Simulation::energy_move`
+}
+
+// Element flags
+pub const CONDUCTIVE: u16 = 1; // Carries sparks
+pub const INSULATOR: u16 = 1 << 1; // Sparks can't jump over it
+pub const SPARK_SOURCE: u16 = 1 << 2; // Sparks conductors next to it
+pub const SEMICONDUCTOR: u16 = 1 << 3; // Only conducts when hot, use with CONDUCTIVE
+pub const AUTOMATON: u16 = 1 << 4; // Lives by the Life rule stored in the particle, see `LifeRule`
+pub const TRANSPARENT: u16 = 1 << 5; // Photons pass through it
+pub const NEUTRON_ABSORBER: u16 = 1 << 6; // Stops neutrons, they pass through everything else
+
+/// Element the particle turns into once the threshold is crossed
+#[derive(Copy, Clone)]
+pub struct Transition {
+	pub threshold: f32,
+	pub into:      u16
+}
+
+/// Blast released by an explosive element
+/// Force and heat fall off linearly to 0 at `radius`
+#[derive(Copy, Clone)]
+pub struct Explosion {
+	pub radius: f32, // Sim pixels
+	pub force:  f32, // Velocity given to particles at the centre
+	pub heat:   f32  // Kelvin added at the centre
 }
 
 #[derive(Clone)]
 pub struct Element {
-	pub id:        u16,
-	pub name:      &'static str,
-	pub col:       [u8; 4],
-	pub behaviour: ElementBehaviour,
-	pub density:   u16,
-	pub update:    Option<fn(pt: &mut Particle)>,
-	pub default:   Particle
+	pub id:            u16,
+	pub name:          &'static str,
+	pub col:           [u8; 4],
+	pub behaviour:     ElementBehaviour,
+	pub density:       u16,
+	pub spread:        u16, // Fluids only, max cells moved sideways per tick
+	pub diffusion:     f32, // Gases only, strength of random movement
+	pub heat_capacity: f32, // Relative, higher heats up slower
+	pub conductivity:  f32, // 0 - 1, part of the temp difference exchanged per tick
+	pub advection:     f32, // How strongly air velocity pushes the particle
+	pub loss:          f32, // 0 - 1, part of the velocity kept every tick, lower means more drag
+	pub bounce:        f32, // 0 - 1, part of the velocity bounced back when hitting something
+	pub friction:      f32, // 0 - 1, part of the velocity along a surface lost when hitting it
+	pub gravity:       f32, // Multiplies gravity, negative floats up
+	pub max_velocity:  f32, /* Terminal velocity in sim pixels per tick, can't go over `MAX_VELOCITY` */
+	pub mass:          f32, // Pulls other particles in Newtonian gravity
+	pub flammability:  f32, // 0 - 1, chance per tick to catch fire next to a burning particle
+	pub burns_into:    u16, // Left behind once burnt out, 0 leaves nothing
+	pub explosion:     Option<Explosion>, // Set off by heat or sparks
+	pub flags:         u16, // Combination of the flags above
+	pub properties:    &'static [Property], // Names and kinds of what's stored in prop1..3
+	pub high_temp:     Option<Transition>, // Kelvin
+	pub low_temp:      Option<Transition>,
+	pub high_pressure: Option<Transition>,
+	pub low_pressure:  Option<Transition>,
+	pub update:        Option<fn(ctx: &mut UpdateContext)>,
+	pub default:       Particle
+}
+
+impl Element {
+	pub fn has_flag(&self, flag: u16) -> bool {
+		self.flags & flag != 0
+	}
 }
 
 pub struct ElementManager {
-	pub elements: Vec<Element>
+	pub elements: Vec<Element>,
+	reactions:    Vec<Vec<Reaction>> // Indexed by type of the first reactant
 }
 
 impl ElementManager {
 	pub fn new() -> Self {
-		Self {
-			elements: vec![EL_NONE, EL_BRCK, EL_DUST, EL_WATR]
+		let mut manager = Self {
+			elements:  vec![
+				EL_NONE, EL_BRCK, EL_DUST, EL_WATR, EL_ICE, EL_WTRV, EL_SALT, EL_SLTW, EL_NBHL,
+				EL_METL, EL_SPRK, EL_BTRY, EL_INSL, EL_NTCT, EL_FIRE, EL_PLSM, EL_SMKE, EL_ASH,
+				EL_WOOD, EL_OIL, EL_GUNP, EL_C4, EL_GOL, EL_HLIF, EL_SEED, EL_PHOT, EL_NEUT,
+				EL_ELEC, EL_RUBL,
+			],
+			reactions: vec![]
+		};
+
+		for reaction in REACTIONS {
+			manager.add_reaction(*reaction);
+		}
+		manager
+	}
+
+	/// Probability is clamped to 0 - 1, NaN never reacts
+	/// Returns false and skips the reaction if any of its types has no element
+	pub fn add_reaction(&mut self, mut reaction: Reaction) -> bool {
+		let types = [reaction.a, reaction.b, reaction.into_a, reaction.into_b];
+		if types
+			.iter()
+			.any(|&t| self.elements.get(t as usize).is_none())
+		{
+			return false;
+		}
+
+		reaction.probability = if reaction.probability.is_nan() {
+			0.0
+		}
+		else {
+			reaction.probability.clamp(0.0, 1.0)
+		};
+		let index = reaction.a as usize;
+		if self.reactions.len() <= index {
+			self.reactions.resize(index + 1, vec![]);
 		}
+		self.reactions[index].push(reaction);
+		true
 	}
 
-	fn get_element(&self, name: &str) -> Option<&Element> {
+	pub fn get_reactions(&self, p_type: u16) -> &[Reaction] {
+		self.reactions
+			.get(p_type as usize)
+			.map_or(&[], |reactions| reactions.as_slice())
+	}
+
+	pub fn get_element(&self, name: &str) -> Option<&Element> {
 		self.elements.iter().find(|x| x.name == name)
 	}
 }
+
+impl Default for ElementManager {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn reaction(a: u16, b: u16, probability: f32) -> Reaction {
+		Reaction {
+			a,
+			b,
+			into_a: EL_NONE.id,
+			into_b: EL_DUST.id,
+			probability,
+			temp_range: None
+		}
+	}
+
+	#[test]
+	fn clamps_probability() {
+		let mut manager = ElementManager::new();
+		assert!(manager.add_reaction(reaction(EL_METL.id, EL_BRCK.id, 2.0)));
+		assert!(manager.add_reaction(reaction(EL_METL.id, EL_WOOD.id, -1.0)));
+		assert!(manager.add_reaction(reaction(EL_METL.id, EL_ICE.id, f32::NAN)));
+
+		let probabilities = manager
+			.get_reactions(EL_METL.id)
+			.iter()
+			.map(|r| r.probability)
+			.collect::<Vec<_>>();
+		assert_eq!(probabilities, [1.0, 0.0, 0.0]);
+	}
+
+	#[test]
+	fn skips_unknown_types() {
+		let mut manager = ElementManager::new();
+		let unknown = manager.elements.len() as u16;
+		assert!(!manager.add_reaction(reaction(unknown, EL_BRCK.id, 1.0)));
+		assert!(!manager.add_reaction(reaction(EL_METL.id, unknown, 1.0)));
+		assert!(!manager.add_reaction(Reaction {
+			into_b: u16::MAX,
+			..reaction(EL_METL.id, EL_BRCK.id, 1.0)
+		}));
+
+		assert!(manager.get_reactions(unknown).is_empty());
+		assert!(manager.get_reactions(EL_METL.id).is_empty());
+	}
+}
@@ -0,0 +1,35 @@
+use crate::simulation::elements::Element;
+use crate::simulation::elements::ElementBehaviour::Solid;
+use crate::simulation::Particle;
+
+// Black hole, only pulls with Newtonian gravity
+pub const ID: u16 = 8;
+pub const EL_NBHL: Element = Element {
+	id:            ID,
+	name:          "NBHL",
+	col:           [72, 24, 96, 255],
+	behaviour:     Solid,
+	density:       100,
+	spread:        0,
+	diffusion:     0.0,
+	heat_capacity: 1.0,
+	conductivity:  0.0,
+	advection:     0.0,
+	loss:          0.0,
+	bounce:        0.0,
+	friction:      0.0,
+	gravity:       0.0,
+	max_velocity:  0.0,
+	mass:          1.0,
+	flammability:  0.0,
+	burns_into:    0,
+	explosion:     None,
+	flags:         0,
+	properties:    &[],
+	high_temp:     None,
+	low_temp:      None,
+	high_pressure: None,
+	low_pressure:  None,
+	update:        None,
+	default:       Particle::default().with_type(ID)
+};
@@ -2,13 +2,33 @@ use crate::simulation::elements::Element;
 use crate::simulation::elements::ElementBehaviour::Skip;
 use crate::simulation::Particle;
 
-const ID: u16 = 0;
+pub const ID: u16 = 0;
 pub const EL_NONE: Element = Element {
-	id:        ID,
-	name:      "NONE",
-	col:       [0, 0, 0, 0],
-	behaviour: Skip,
-	density:   0,
-	update:    None,
-	default:   Particle::default()
+	id:            ID,
+	name:          "NONE",
+	col:           [0, 0, 0, 0],
+	behaviour:     Skip,
+	density:       0,
+	spread:        0,
+	diffusion:     0.0,
+	heat_capacity: 0.0,
+	conductivity:  0.0,
+	advection:     0.0,
+	loss:          0.0,
+	bounce:        0.0,
+	friction:      0.0,
+	gravity:       0.0,
+	max_velocity:  0.0,
+	mass:          0.0,
+	flammability:  0.0,
+	burns_into:    0,
+	explosion:     None,
+	flags:         0,
+	properties:    &[],
+	high_temp:     None,
+	low_temp:      None,
+	high_pressure: None,
+	low_pressure:  None,
+	update:        None,
+	default:       Particle::default()
 };
@@ -0,0 +1,36 @@
+use crate::simulation::elements::ElementBehaviour::Solid;
+use crate::simulation::elements::{Element, CONDUCTIVE, SEMICONDUCTOR};
+use crate::simulation::properties::LIFE;
+use crate::simulation::Particle;
+
+// Semiconductor, only conducts above 100C
+pub const ID: u16 = 13;
+pub const EL_NTCT: Element = Element {
+	id:            ID,
+	name:          "NTCT",
+	col:           [80, 80, 80, 255],
+	behaviour:     Solid,
+	density:       100,
+	spread:        0,
+	diffusion:     0.0,
+	heat_capacity: 0.7,
+	conductivity:  0.6,
+	advection:     0.0,
+	loss:          0.0,
+	bounce:        0.0,
+	friction:      0.0,
+	gravity:       0.0,
+	max_velocity:  0.0,
+	mass:          0.0,
+	flammability:  0.0,
+	burns_into:    0,
+	explosion:     None,
+	flags:         CONDUCTIVE | SEMICONDUCTOR,
+	properties:    &[LIFE],
+	high_temp:     None,
+	low_temp:      None,
+	high_pressure: None,
+	low_pressure:  None,
+	update:        None,
+	default:       Particle::default().with_type(ID)
+};
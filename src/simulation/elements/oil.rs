@@ -0,0 +1,35 @@
+use crate::simulation::elements::ElementBehaviour::Fluid;
+use crate::simulation::elements::{smke, Element};
+use crate::simulation::Particle;
+
+// Floats on water and burns quickly into smoke
+pub const ID: u16 = 19;
+pub const EL_OIL: Element = Element {
+	id:            ID,
+	name:          "OIL",
+	col:           [64, 64, 16, 255],
+	behaviour:     Fluid,
+	density:       4,
+	spread:        4,
+	diffusion:     0.0,
+	heat_capacity: 2.0,
+	conductivity:  0.04,
+	advection:     0.05,
+	loss:          0.98,
+	bounce:        0.0,
+	friction:      0.2,
+	gravity:       1.0,
+	max_velocity:  10.0,
+	mass:          0.0,
+	flammability:  0.2,
+	burns_into:    smke::ID,
+	explosion:     None,
+	flags:         0,
+	properties:    &[],
+	high_temp:     None,
+	low_temp:      None,
+	high_pressure: None,
+	low_pressure:  None,
+	update:        None,
+	default:       Particle::default().with_type(ID)
+};
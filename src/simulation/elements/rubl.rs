@@ -0,0 +1,35 @@
+use crate::simulation::elements::Element;
+use crate::simulation::elements::ElementBehaviour::Powder;
+use crate::simulation::Particle;
+
+// Left by solids broken by a blast
+pub const ID: u16 = 28;
+pub const EL_RUBL: Element = Element {
+	id:            ID,
+	name:          "RUBL",
+	col:           [104, 100, 96, 255],
+	behaviour:     Powder,
+	density:       18,
+	spread:        0,
+	diffusion:     0.0,
+	heat_capacity: 0.84,
+	conductivity:  0.5,
+	advection:     0.05,
+	loss:          0.95,
+	bounce:        0.2,
+	friction:      0.7,
+	gravity:       1.0,
+	max_velocity:  10.0,
+	mass:          0.0,
+	flammability:  0.0,
+	burns_into:    0,
+	explosion:     None,
+	flags:         0,
+	properties:    &[],
+	high_temp:     None,
+	low_temp:      None,
+	high_pressure: None,
+	low_pressure:  None,
+	update:        None,
+	default:       Particle::default().with_type(ID)
+};
@@ -0,0 +1,34 @@
+use crate::simulation::elements::Element;
+use crate::simulation::elements::ElementBehaviour::Powder;
+use crate::simulation::Particle;
+
+pub const ID: u16 = 6;
+pub const EL_SALT: Element = Element {
+	id:            ID,
+	name:          "SALT",
+	col:           [255, 255, 255, 255],
+	behaviour:     Powder,
+	density:       10,
+	spread:        0,
+	diffusion:     0.0,
+	heat_capacity: 0.88,
+	conductivity:  0.43,
+	advection:     0.1,
+	loss:          0.95,
+	bounce:        0.3,
+	friction:      0.5,
+	gravity:       1.0,
+	max_velocity:  10.0,
+	mass:          0.0,
+	flammability:  0.0,
+	burns_into:    0,
+	explosion:     None,
+	flags:         0,
+	properties:    &[],
+	high_temp:     None,
+	low_temp:      None,
+	high_pressure: None,
+	low_pressure:  None,
+	update:        None,
+	default:       Particle::default().with_type(ID)
+};
@@ -0,0 +1,35 @@
+use crate::simulation::automata::{life_cell, BIRTH, SURVIVAL};
+use crate::simulation::elements::ElementBehaviour::Solid;
+use crate::simulation::elements::{Element, AUTOMATON};
+
+// Every cell dies right away, patterns explode into noise
+pub const ID: u16 = 24;
+pub const EL_SEED: Element = Element {
+	id:            ID,
+	name:          "SEED",
+	col:           [251, 236, 125, 255],
+	behaviour:     Solid,
+	density:       100,
+	spread:        0,
+	diffusion:     0.0,
+	heat_capacity: 1.0,
+	conductivity:  0.0,
+	advection:     0.0,
+	loss:          0.0,
+	bounce:        0.0,
+	friction:      0.0,
+	gravity:       0.0,
+	max_velocity:  0.0,
+	mass:          0.0,
+	flammability:  0.0,
+	burns_into:    0,
+	explosion:     None,
+	flags:         AUTOMATON,
+	properties:    &[BIRTH, SURVIVAL],
+	high_temp:     None,
+	low_temp:      None,
+	high_pressure: None,
+	low_pressure:  None,
+	update:        None,
+	default:       life_cell(ID, "B2/S")
+};
@@ -0,0 +1,43 @@
+use crate::simulation::elements::ElementBehaviour::Fluid;
+use crate::simulation::elements::{
+	ice, wtrv, Element, Transition, CONDUCTIVE, NEUTRON_ABSORBER, TRANSPARENT
+};
+use crate::simulation::properties::LIFE;
+use crate::simulation::Particle;
+
+pub const ID: u16 = 7;
+pub const EL_SLTW: Element = Element {
+	id:            ID,
+	name:          "SLTW",
+	col:           [64, 80, 240, 200],
+	behaviour:     Fluid,
+	density:       6,
+	spread:        5,
+	diffusion:     0.0,
+	heat_capacity: 3.99,
+	conductivity:  0.29,
+	advection:     0.05,
+	loss:          0.98,
+	bounce:        0.0,
+	friction:      0.1,
+	gravity:       1.0,
+	max_velocity:  10.0,
+	mass:          0.0,
+	flammability:  0.0,
+	burns_into:    0,
+	explosion:     None,
+	flags:         CONDUCTIVE | TRANSPARENT | NEUTRON_ABSORBER,
+	properties:    &[LIFE],
+	high_temp:     Some(Transition {
+		threshold: 383.0,
+		into:      wtrv::ID
+	}),
+	low_temp:      Some(Transition {
+		threshold: 252.05,
+		into:      ice::ID
+	}),
+	high_pressure: None,
+	low_pressure:  None,
+	update:        None,
+	default:       Particle::default().with_type(ID)
+};
@@ -0,0 +1,53 @@
+use rand::Rng;
+
+use crate::simulation::elements::Element;
+use crate::simulation::elements::ElementBehaviour::Gas;
+use crate::simulation::properties::LIFE;
+use crate::simulation::{Particle, UpdateContext};
+
+// Left by fire, fades away after a while
+pub const ID: u16 = 16;
+pub const EL_SMKE: Element = Element {
+	id:            ID,
+	name:          "SMKE",
+	col:           [64, 64, 64, 160],
+	behaviour:     Gas,
+	density:       1,
+	spread:        0,
+	diffusion:     0.4,
+	heat_capacity: 1.0,
+	conductivity:  0.1,
+	advection:     0.4,
+	loss:          0.9,
+	bounce:        0.0,
+	friction:      0.0,
+	gravity:       -0.3,
+	max_velocity:  3.0,
+	mass:          0.0,
+	flammability:  0.0,
+	burns_into:    0,
+	explosion:     None,
+	flags:         0,
+	properties:    &[LIFE],
+	high_temp:     None,
+	low_temp:      None,
+	high_pressure: None,
+	low_pressure:  None,
+	update:        Some(update),
+	default:       Particle::default().with_type(ID)
+};
+
+// Life counts down the ticks left, smoke made with life 0 gets a random one
+fn update(ctx: &mut UpdateContext) {
+	let life = LIFE.get(ctx.part());
+	match life {
+		0 => {
+			let life = ctx.rng().gen_range(250..300);
+			ctx.set(ctx.id(), LIFE, life);
+		}
+		1 => ctx.kill_self(),
+		_ => {
+			ctx.set(ctx.id(), LIFE, life - 1);
+		}
+	}
+}
@@ -0,0 +1,36 @@
+use crate::simulation::elements::Element;
+use crate::simulation::elements::ElementBehaviour::Solid;
+use crate::simulation::properties::{CTYPE, LIFE, TMP};
+use crate::simulation::Particle;
+
+// Spark travelling through a conductor, turns back into it after a few ticks
+pub const ID: u16 = 10;
+pub const EL_SPRK: Element = Element {
+	id:            ID,
+	name:          "SPRK",
+	col:           [255, 255, 128, 255],
+	behaviour:     Solid,
+	density:       100,
+	spread:        0,
+	diffusion:     0.0,
+	heat_capacity: 0.45,
+	conductivity:  0.5,
+	advection:     0.0,
+	loss:          0.0,
+	bounce:        0.0,
+	friction:      0.0,
+	gravity:       0.0,
+	max_velocity:  0.0,
+	mass:          0.0,
+	flammability:  0.0,
+	burns_into:    0,
+	explosion:     None,
+	flags:         0,
+	properties:    &[CTYPE, LIFE, TMP],
+	high_temp:     None,
+	low_temp:      None,
+	high_pressure: None,
+	low_pressure:  None,
+	update:        None,
+	default:       Particle::default().with_type(ID)
+};
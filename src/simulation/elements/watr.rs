@@ -1,16 +1,45 @@
-use crate::simulation::elements::Element;
 use crate::simulation::elements::ElementBehaviour::Fluid;
+use crate::simulation::elements::{
+	ice, wtrv, Element, Transition, CONDUCTIVE, NEUTRON_ABSORBER, TRANSPARENT
+};
+use crate::simulation::properties::LIFE;
 use crate::simulation::Particle;
 
-const ID: u16 = 3;
+pub const ID: u16 = 3;
 pub const EL_WATR: Element = Element {
-	id:        ID,
-	name:      "WATR",
-	col:       [0, 0, 255, 200],
-	behaviour: Fluid,
-	density:   5,
-	update:    None,
-	default:   Particle::default().with_type(ID)
+	id:            ID,
+	name:          "WATR",
+	col:           [0, 0, 255, 200],
+	behaviour:     Fluid,
+	density:       5,
+	spread:        5,
+	diffusion:     0.0,
+	heat_capacity: 4.18,
+	conductivity:  0.11,
+	advection:     0.05,
+	loss:          0.98,
+	bounce:        0.0,
+	friction:      0.1,
+	gravity:       1.0,
+	max_velocity:  10.0,
+	mass:          0.0,
+	flammability:  0.0,
+	burns_into:    0,
+	explosion:     None,
+	flags:         CONDUCTIVE | TRANSPARENT | NEUTRON_ABSORBER,
+	properties:    &[LIFE],
+	high_temp:     Some(Transition {
+		threshold: 373.15,
+		into:      wtrv::ID
+	}),
+	low_temp:      Some(Transition {
+		threshold: 273.15,
+		into:      ice::ID
+	}),
+	high_pressure: None,
+	low_pressure:  None,
+	update:        None,
+	default:       Particle::default().with_type(ID)
 };
 
 // pub fn gfx(sim : &Simulation, pt : &Particle) -> Color{
@@ -0,0 +1,35 @@
+use crate::simulation::elements::ElementBehaviour::Solid;
+use crate::simulation::elements::{ash, Element};
+use crate::simulation::Particle;
+
+// Burns slowly into ash
+pub const ID: u16 = 18;
+pub const EL_WOOD: Element = Element {
+	id:            ID,
+	name:          "WOOD",
+	col:           [192, 160, 64, 255],
+	behaviour:     Solid,
+	density:       100,
+	spread:        0,
+	diffusion:     0.0,
+	heat_capacity: 1.7,
+	conductivity:  0.08,
+	advection:     0.0,
+	loss:          0.0,
+	bounce:        0.0,
+	friction:      0.0,
+	gravity:       0.0,
+	max_velocity:  0.0,
+	mass:          0.0,
+	flammability:  0.02,
+	burns_into:    ash::ID,
+	explosion:     None,
+	flags:         0,
+	properties:    &[],
+	high_temp:     None,
+	low_temp:      None,
+	high_pressure: None,
+	low_pressure:  None,
+	update:        None,
+	default:       Particle::default().with_type(ID)
+};
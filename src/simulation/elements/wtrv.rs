@@ -0,0 +1,37 @@
+use crate::simulation::elements::ElementBehaviour::Gas;
+use crate::simulation::elements::{watr, Element, Transition, TRANSPARENT};
+use crate::simulation::Particle;
+
+pub const ID: u16 = 5;
+pub const EL_WTRV: Element = Element {
+	id:            ID,
+	name:          "WTRV",
+	col:           [160, 160, 255, 255],
+	behaviour:     Gas,
+	density:       1,
+	spread:        0,
+	diffusion:     0.5,
+	heat_capacity: 2.0,
+	conductivity:  0.19,
+	advection:     0.2,
+	loss:          0.9,
+	bounce:        0.0,
+	friction:      0.0,
+	gravity:       -0.5,
+	max_velocity:  4.0,
+	mass:          0.0,
+	flammability:  0.0,
+	burns_into:    0,
+	explosion:     None,
+	flags:         TRANSPARENT,
+	properties:    &[],
+	high_temp:     None,
+	low_temp:      Some(Transition {
+		threshold: 371.15,
+		into:      watr::ID
+	}),
+	high_pressure: None,
+	low_pressure:  None,
+	update:        None,
+	default:       Particle::default().with_type(ID).with_temp(383.15)
+};
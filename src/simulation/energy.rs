@@ -0,0 +1,180 @@
+use std::f32::consts::TAU;
+use std::ops::Range;
+
+use rand::Rng;
+use rust_bresenham::Bresenham;
+
+use crate::simulation::elements::*;
+use crate::simulation::heat::{MAX_TEMP, MIN_TEMP};
+use crate::simulation::properties::LIFE;
+use crate::simulation::region::Region;
+use crate::simulation::sim::{EdgeMode, ParticleHandle, Simulation};
+
+pub const SPEED: f32 = 3.0; // Sim pixels per tick
+
+const LIFETIME: Range<u16> = 400..700; // Ticks
+const ABSORB_HEAT: f32 = 10.0; // Kelvin given to the particle that absorbs energy
+
+// Energy particles are kept in `Simulation::photons` instead of pmap, so they can overlap matter
+// They use life for ticks left, ones made with life 0 get a random life and direction on their first update
+impl Simulation {
+	/// Energy particle at x, y, there can be more than one, this returns the last one that got there
+	pub fn get_photon(&self, x: usize, y: usize) -> Option<ParticleHandle> {
+		if x >= self.get_width() || y >= self.get_height() {
+			return None;
+		}
+
+		self.photons[x + y * self.get_width()]
+	}
+
+	pub(super) fn is_energy(&self, id: ParticleHandle) -> bool {
+		let el = self.parts[id].get_type(&self.element_manager);
+		matches!(el.behaviour, ElementBehaviour::Energy)
+	}
+}
+
+impl Region<'_> {
+	pub(super) fn is_energy(&self, id: ParticleHandle) -> bool {
+		let el = self.parts[id].get_type(self.element_manager);
+		matches!(el.behaviour, ElementBehaviour::Energy)
+	}
+
+	/// Moves the particle in a straight line at `SPEED` until something absorbs it
+	pub(super) fn energy_move(&mut self, pt_id: ParticleHandle) {
+		let life = LIFE.get(&self.parts[pt_id]);
+		if life == 1 {
+			self.kill_part(pt_id).expect("Tried to kill invalid part");
+			return;
+		}
+		if life == 0 {
+			self.launch(pt_id);
+		}
+		else {
+			LIFE.set(&mut self.parts[pt_id], life - 1);
+		}
+
+		let pt = &self.parts[pt_id];
+		let (vx, vy) = (pt.vx, pt.vy);
+		let (px, py) = (pt.x as isize, pt.y as isize);
+		let (rx, ry) = ((pt.x + vx) as isize, (pt.y + vy) as isize);
+		let mut dest = (pt.x + vx, pt.y + vy);
+
+		let mut points: Vec<_> = Bresenham::new((px, py), (rx, ry)).collect();
+		points.push((rx, ry));
+
+		let mut prev = (px, py);
+		for (x, y) in points.into_iter().skip(1) {
+			let Some((bx, by)) = self.wrap_pos(x, y)
+			else {
+				if self.edge_mode == EdgeMode::Void {
+					self.kill_part(pt_id).expect("Tried to kill invalid part");
+					return;
+				}
+
+				// Bounces off solid edges
+				let (w, h) = (self.width as isize, self.height as isize);
+				let pt = &mut self.parts[pt_id];
+				if x < 0 || x >= w {
+					pt.vx = -pt.vx;
+				}
+				if y < 0 || y >= h {
+					pt.vy = -pt.vy;
+				}
+				dest = (prev.0 as f32, prev.1 as f32);
+				break;
+			};
+			// Carries on next tick from the chunk it ends up in
+			if !self.in_reach(bx, by) {
+				dest = (prev.0 as f32, prev.1 as f32);
+				break;
+			}
+
+			let absorbed = self.wall_blocks(pt_id, bx, by) ||
+				self.get_id(bx, by)
+					.is_some_and(|other| self.hit(pt_id, other, bx, by));
+			if absorbed {
+				self.kill_part(pt_id).expect("Tried to kill invalid part");
+				return;
+			}
+
+			prev = (x, y);
+		}
+
+		self.move_photon(pt_id, dest);
+	}
+
+	// Random direction if the particle isn't moving, otherwise its speed gets set to SPEED
+	fn launch(&mut self, pt_id: ParticleHandle) {
+		let life = self.rng().gen_range(LIFETIME);
+		let angle = self.rng().gen_range(0.0..TAU);
+
+		let pt = &mut self.parts[pt_id];
+		LIFE.set(pt, life);
+
+		let speed = (pt.vx * pt.vx + pt.vy * pt.vy).sqrt();
+		(pt.vx, pt.vy) = if speed > 0.0 {
+			(pt.vx / speed * SPEED, pt.vy / speed * SPEED)
+		}
+		else {
+			(angle.cos() * SPEED, angle.sin() * SPEED)
+		};
+	}
+
+	/// Energy particle passing matter at x, y
+	/// Returns true if the energy got absorbed
+	fn hit(&mut self, pt_id: ParticleHandle, other: ParticleHandle, x: usize, y: usize) -> bool {
+		let p_type = self.parts[pt_id].p_type;
+		let el = self.parts[other].get_type(self.element_manager);
+
+		let absorbed = match p_type {
+			id if id == EL_PHOT.id => !el.has_flag(TRANSPARENT),
+			id if id == EL_NEUT.id => el.has_flag(NEUTRON_ABSORBER),
+			// Electrons spark conductors and set off explosives
+			id if id == EL_ELEC.id => {
+				self.spark(x, y);
+				true
+			}
+			_ => false
+		};
+
+		if absorbed {
+			if let Some(pt) = self.parts.get_mut(other).filter(|pt| pt.p_type != 0) {
+				pt.temp = (pt.temp + ABSORB_HEAT).clamp(MIN_TEMP, MAX_TEMP);
+			}
+		}
+		absorbed
+	}
+
+	fn move_photon(&mut self, pt_id: ParticleHandle, (x, y): (f32, f32)) {
+		let width = self.width;
+		let pt = &self.parts[pt_id];
+		let old = pt.x as usize + pt.y as usize * width;
+		if self.photons[old] == Some(pt_id) {
+			self.photons[old] = None;
+		}
+
+		let (w, h) = (width as f32, self.height as f32);
+		let (x, y) = if self.edge_mode == EdgeMode::Wrap {
+			// Tiny negative values can round up to the size
+			(
+				Some(x.rem_euclid(w)).filter(|x| *x < w).unwrap_or(0.0),
+				Some(y.rem_euclid(h)).filter(|y| *y < h).unwrap_or(0.0)
+			)
+		}
+		else {
+			// Positions just outside the board still round to a pixel on it
+			(x.clamp(0.0, w - 1.0), y.clamp(0.0, h - 1.0))
+		};
+		let (x, y) = if self.in_reach(x as usize, y as usize) {
+			(x, y)
+		}
+		else {
+			(self.parts[pt_id].x, self.parts[pt_id].y)
+		};
+
+		let pt = &mut self.parts[pt_id];
+		pt.x = x;
+		pt.y = y;
+		self.photons[pt.x as usize + pt.y as usize * width] = Some(pt_id);
+	}
+}
@@ -0,0 +1,138 @@
+use crate::simulation::combustion::IGNITION_TEMP;
+use crate::simulation::elements::{ElementBehaviour, Explosion, EL_RUBL};
+use crate::simulation::heat::{MAX_TEMP, MIN_TEMP};
+use crate::simulation::region::Region;
+use crate::simulation::sim::{ParticleHandle, Simulation};
+
+const BREAK_FORCE: f32 = 2.0; // Solids hit harder than this break
+const PRESSURE: f32 = 4.0; // Air pressure added at the centre per unit of force
+
+/// Explosion waiting to be applied once the particle update is done
+#[derive(Copy, Clone)]
+pub(super) struct Blast {
+	x:         f32,
+	y:         f32,
+	explosion: Explosion
+}
+
+impl Simulation {
+	/// Explodes the particle, the blast happens at the end of the tick
+	/// Returns false if the particle isn't explosive
+	pub fn detonate(&mut self, id: ParticleHandle) -> bool {
+		self.region().detonate(id)
+	}
+
+	/// Blasts reach further than a chunk is allowed to, so they're applied one by one after the particle update
+	pub(super) fn apply_blasts(&mut self) {
+		for chunk in self.chunks.iter_mut() {
+			self.blasts.append(&mut chunk.blasts);
+		}
+
+		// Explosives caught in a blast queue their own, so this runs until the chain is over
+		while !self.blasts.is_empty() {
+			for blast in std::mem::take(&mut self.blasts) {
+				self.apply_blast(blast);
+			}
+		}
+	}
+
+	/// Pushes particles away from the centre, heats them, breaks solids and sets off explosives
+	fn apply_blast(&mut self, blast: Blast) {
+		let Explosion {
+			radius,
+			force,
+			heat
+		} = blast.explosion;
+		let (x, y) = (blast.x as isize, blast.y as isize);
+		self.air
+			.add_pressure(x as usize, y as usize, force * PRESSURE);
+
+		let reach = radius.ceil() as isize;
+		for dy in -reach..=reach {
+			for dx in -reach..=reach {
+				let dist = ((dx * dx + dy * dy) as f32).sqrt();
+				if dist > radius {
+					continue;
+				}
+				let Some(id) = self.get_id_wrapped(x + dx, y + dy)
+				else {
+					continue;
+				};
+
+				if self.detonate(id) {
+					continue;
+				}
+
+				let falloff = 1.0 - dist / radius;
+				let pt = &mut self.parts[id];
+				pt.temp = (pt.temp + heat * falloff).clamp(MIN_TEMP, MAX_TEMP);
+
+				let el = pt.get_type(&self.element_manager);
+				if !matches!(el.behaviour, ElementBehaviour::Solid) {
+					// velocity_move moves it on its next update
+					if dist > 0.0 {
+						pt.vx += dx as f32 / dist * force * falloff;
+						pt.vy += dy as f32 / dist * force * falloff;
+					}
+					continue;
+				}
+
+				// Broken solids turn into what high pressure turns them into, rubble otherwise
+				if force * falloff > BREAK_FORCE {
+					let into = el.high_pressure.map_or(EL_RUBL.id, |t| t.into);
+					self.change_type(id, into);
+				}
+			}
+		}
+	}
+}
+
+impl Region<'_> {
+	pub(super) fn detonate(&mut self, id: ParticleHandle) -> bool {
+		let pt = &self.parts[id];
+		let Some(explosion) = pt.get_type(self.element_manager).explosion
+		else {
+			return false;
+		};
+
+		let blast = Blast {
+			x: pt.x,
+			y: pt.y,
+			explosion
+		};
+		self.add_blast(blast);
+		self.kill_part(id).expect("Tried to kill invalid part");
+		true
+	}
+
+	/// Sets off explosives hotter than `IGNITION_TEMP`
+	/// Returns true if the particle was killed
+	pub(super) fn update_explosives(&mut self, pt_id: ParticleHandle) -> bool {
+		self.parts[pt_id].temp > IGNITION_TEMP && self.detonate(pt_id)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::simulation::elements::{EL_BRCK, EL_C4, EL_METL};
+
+	#[test]
+	fn blast_sets_off_explosives_in_reach() {
+		let mut sim = Simulation::new(64, 64);
+		let first = sim.create_part(EL_C4.id, 20, 20).unwrap();
+		let second = sim.create_part(EL_C4.id, 30, 20).unwrap();
+		let near = sim.create_part(EL_METL.id, 20, 25).unwrap();
+		// Only the second blast reaches it
+		let far = sim.create_part(EL_BRCK.id, 42, 20).unwrap();
+
+		assert!(sim.detonate(first));
+		sim.apply_blasts();
+
+		assert_eq!(sim.parts[first].p_type, 0);
+		assert_eq!(sim.parts[second].p_type, 0);
+		assert_eq!(sim.parts[near].p_type, EL_RUBL.id);
+		assert_eq!(sim.parts[far].p_type, EL_RUBL.id);
+		assert!(sim.blasts.is_empty());
+	}
+}
@@ -0,0 +1,134 @@
+use crate::simulation::elements::ElementManager;
+use crate::simulation::Particle;
+
+pub const DEFAULT_STRENGTH: f32 = 0.1;
+pub const CELL: usize = 16; // Size of a Newtonian gravity cell in sim pixels
+
+const G: f32 = 1.0;
+const SOFTENING: f32 = 1.0; // In cells, keeps the pull finite right next to a mass
+
+/// Direction particles get pulled in
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GravityMode {
+	Off,
+	Vertical,
+	Radial,    // Toward the centre of the board
+	Newtonian  // Toward elements with mass
+}
+
+/// Acceleration of particles in sim pixels per tick squared
+pub struct Gravity {
+	pub mode:      GravityMode,
+	pub strength:  f32,        // Vertical and radial only, negative pushes up or away
+	pub potential: Box<[f32]>, // Newtonian only, on a grid of `CELL`x`CELL` sim pixels
+
+	mass:    Box<[f32]>,
+	field_x: Box<[f32]>,
+	field_y: Box<[f32]>,
+	width:   usize, // In cells
+	height:  usize,
+	centre:  (f32, f32)
+}
+
+impl Gravity {
+	/// Takes the size of the simulation in sim pixels
+	pub fn new(width: usize, height: usize) -> Self {
+		let centre = (width as f32 / 2.0, height as f32 / 2.0);
+		let (width, height) = (width.div_ceil(CELL), height.div_ceil(CELL));
+		let grid = vec![0f32; width * height].into_boxed_slice();
+
+		Self {
+			mode: GravityMode::Vertical,
+			strength: DEFAULT_STRENGTH,
+			potential: grid.clone(),
+			mass: grid.clone(),
+			field_x: grid.clone(),
+			field_y: grid,
+			width,
+			height,
+			centre
+		}
+	}
+
+	/// Pull at sim position x, y
+	pub fn at(&self, x: f32, y: f32) -> (f32, f32) {
+		match self.mode {
+			GravityMode::Off => (0.0, 0.0),
+			GravityMode::Vertical => (0.0, self.strength),
+			GravityMode::Radial => {
+				let (dx, dy) = (self.centre.0 - x, self.centre.1 - y);
+				let dist = (dx * dx + dy * dy).sqrt();
+				if dist < 1.0 {
+					return (0.0, 0.0);
+				}
+				(dx / dist * self.strength, dy / dist * self.strength)
+			}
+			GravityMode::Newtonian => {
+				let (cx, cy) = (x as usize / CELL, y as usize / CELL);
+				if cx >= self.width || cy >= self.height {
+					return (0.0, 0.0);
+				}
+				let i = cx + cy * self.width;
+				(self.field_x[i], self.field_y[i])
+			}
+		}
+	}
+
+	/// Recomputes the Newtonian field from the mass of live particles
+	/// Does nothing in other modes
+	pub fn update(&mut self, parts: &[Particle], elements: &ElementManager) {
+		if self.mode != GravityMode::Newtonian {
+			return;
+		}
+
+		self.mass.fill(0.0);
+		for pt in parts.iter().filter(|pt| pt.p_type != 0) {
+			let mass = pt.get_type(elements).mass;
+			if mass != 0.0 {
+				let (cx, cy) = (pt.x as usize / CELL, pt.y as usize / CELL);
+				self.mass[cx + cy * self.width] += mass;
+			}
+		}
+
+		// Most of the board is usually empty, so only cells with mass are summed
+		let sources: Vec<(f32, f32, f32)> = (0..self.mass.len())
+			.filter(|&i| self.mass[i] != 0.0)
+			.map(|i| {
+				(
+					(i % self.width) as f32,
+					(i / self.width) as f32,
+					self.mass[i]
+				)
+			})
+			.collect();
+
+		for i in 0..self.potential.len() {
+			let (x, y) = ((i % self.width) as f32, (i / self.width) as f32);
+			self.potential[i] = sources
+				.iter()
+				.map(|(sx, sy, mass)| {
+					let dist_sq = (sx - x).powi(2) + (sy - y).powi(2) + SOFTENING * SOFTENING;
+					-G * mass / dist_sq.sqrt()
+				})
+				.sum();
+		}
+
+		// Particles are pulled down the potential
+		let (width, height) = (self.width, self.height);
+		for y in 0..height {
+			for x in 0..width {
+				let i = x + y * width;
+				let (left, right) = (x.saturating_sub(1), (x + 1).min(width - 1));
+				let (up, down) = (y.saturating_sub(1), (y + 1).min(height - 1));
+
+				let dx = self.potential[right + y * width] - self.potential[left + y * width];
+				let dy = self.potential[x + down * width] - self.potential[x + up * width];
+				let spacing_x = ((right - left).max(1) * CELL) as f32;
+				let spacing_y = ((down - up).max(1) * CELL) as f32;
+
+				self.field_x[i] = -dx / spacing_x;
+				self.field_y[i] = -dy / spacing_y;
+			}
+		}
+	}
+}
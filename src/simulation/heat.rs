@@ -0,0 +1,45 @@
+use crate::simulation::region::Region;
+use crate::simulation::sim::ParticleHandle;
+
+// All temperatures are in Kelvin
+pub const R_TEMP: f32 = 295.15; // Room temperature, new particles start with it
+pub const MIN_TEMP: f32 = 0.0;
+pub const MAX_TEMP: f32 = 9999.0;
+
+impl Region<'_> {
+	/// Exchanges heat between neighbouring particles of a chunk
+	/// Every pair is visited once (right and bottom neighbour) and the exchanged energy is conserved
+	pub(super) fn conduct_heat(&mut self) {
+		for i in 0..self.chunk_parts().len() {
+			let pt_id = self.chunk_parts()[i];
+			if !self.parts.owns(pt_id) {
+				continue;
+			}
+			let (x, y) = (self.parts[pt_id].x as isize, self.parts[pt_id].y as isize);
+			for (nx, ny) in [(x + 1, y), (x, y + 1)] {
+				if let Some(other) = self.get_id_wrapped(nx, ny) {
+					self.exchange_heat(pt_id, other);
+				}
+			}
+		}
+	}
+
+	fn exchange_heat(&mut self, a: ParticleHandle, b: ParticleHandle) {
+		let el_a = self.parts[a].get_type(self.element_manager);
+		let el_b = self.parts[b].get_type(self.element_manager);
+
+		let conductivity = el_a.conductivity.min(el_b.conductivity);
+		let (cap_a, cap_b) = (el_a.heat_capacity, el_b.heat_capacity);
+		if conductivity <= 0.0 || cap_a + cap_b <= 0.0 {
+			return;
+		}
+
+		let (temp_a, temp_b) = (self.parts[a].temp, self.parts[b].temp);
+		let equilibrium = (cap_a * temp_a + cap_b * temp_b) / (cap_a + cap_b);
+
+		self.parts[a].temp =
+			(temp_a + (equilibrium - temp_a) * conductivity).clamp(MIN_TEMP, MAX_TEMP);
+		self.parts[b].temp =
+			(temp_b + (equilibrium - temp_b) * conductivity).clamp(MIN_TEMP, MAX_TEMP);
+	}
+}
@@ -0,0 +1,198 @@
+use std::collections::VecDeque;
+use std::mem::{size_of, size_of_val};
+
+use crate::simulation::sim::Simulation;
+use crate::simulation::walls::{WallType, Walls};
+use crate::simulation::Particle;
+
+/// State of the board that edits can change, settings like gravity or edge mode aren't included
+pub struct Snapshot {
+	parts:    Box<[Particle]>, // Up to the last live particle, so handles stay the same
+	pressure: Box<[f32]>,
+	air_vx:   Box<[f32]>,
+	air_vy:   Box<[f32]>,
+	walls:    Walls
+}
+
+impl Snapshot {
+	/// Approximate memory used in bytes
+	pub fn size(&self) -> usize {
+		let air =
+			size_of_val(&*self.pressure) + size_of_val(&*self.air_vx) + size_of_val(&*self.air_vy);
+		// Walls use the same grid as air
+		let walls = self.pressure.len() * (size_of::<WallType>() + size_of::<u16>());
+		size_of_val(&*self.parts) + air + walls
+	}
+}
+
+/// Undo and redo stacks, the oldest snapshots are dropped once they take more than the budget
+pub struct History {
+	undo:   VecDeque<Snapshot>,
+	redo:   Vec<Snapshot>,
+	budget: usize, // Bytes
+	used:   usize
+}
+
+impl History {
+	pub fn new(budget: usize) -> Self {
+		Self {
+			undo: VecDeque::new(),
+			redo: Vec::new(),
+			budget,
+			used: 0
+		}
+	}
+
+	/// Saves the simulation before an edit, clears what could be redone
+	pub fn record(&mut self, sim: &Simulation) {
+		self.record_snapshot(sim.snapshot());
+	}
+
+	/// Same as `record`, for edits that can fail, the snapshot is taken before and recorded once it succeeded
+	pub fn record_snapshot(&mut self, snapshot: Snapshot) {
+		self.used -= self.redo.drain(..).map(|s| s.size()).sum::<usize>();
+		self.push_undo(snapshot);
+	}
+
+	/// Goes back to the last recorded state
+	/// Returns false if there's nothing to undo
+	pub fn undo(&mut self, sim: &mut Simulation) -> bool {
+		let Some(snapshot) = self.undo.pop_back()
+		else {
+			return false;
+		};
+
+		let current = sim.snapshot();
+		self.used += current.size();
+		self.used -= snapshot.size();
+		self.redo.push(current);
+		sim.restore(&snapshot);
+		self.trim();
+		true
+	}
+
+	/// Goes back to the state before the last undo
+	/// Returns false if there's nothing to redo
+	pub fn redo(&mut self, sim: &mut Simulation) -> bool {
+		let Some(snapshot) = self.redo.pop()
+		else {
+			return false;
+		};
+
+		self.used -= snapshot.size();
+		self.push_undo(sim.snapshot());
+		sim.restore(&snapshot);
+		true
+	}
+
+	fn push_undo(&mut self, snapshot: Snapshot) {
+		self.used += snapshot.size();
+		self.undo.push_back(snapshot);
+		self.trim();
+	}
+
+	// Redo is only ever as big as what was undone, so only undo gets trimmed
+	fn trim(&mut self) {
+		while self.used > self.budget {
+			let Some(oldest) = self.undo.pop_front()
+			else {
+				break;
+			};
+			self.used -= oldest.size();
+		}
+	}
+}
+
+impl Simulation {
+	pub fn snapshot(&self) -> Snapshot {
+		Snapshot {
+			parts:    self.parts[..self.get_parts_end()].into(),
+			pressure: self.air.pressure.clone(),
+			air_vx:   self.air.vx.clone(),
+			air_vy:   self.air.vy.clone(),
+			walls:    self.walls.clone()
+		}
+	}
+
+	/// Puts the board back into the state it was in when the snapshot was taken
+	pub fn restore(&mut self, snapshot: &Snapshot) {
+		self.replace_parts(&snapshot.parts);
+		self.air.pressure.copy_from_slice(&snapshot.pressure);
+		self.air.vx.copy_from_slice(&snapshot.air_vx);
+		self.air.vy.copy_from_slice(&snapshot.air_vy);
+		self.walls.copy_from(&snapshot.walls);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::simulation::elements::EL_DUST;
+
+	fn board(sim: &Simulation) -> Vec<(u16, f32, f32)> {
+		sim.iter_parts()
+			.map(|(_, pt)| (pt.p_type, pt.x, pt.y))
+			.collect()
+	}
+
+	#[test]
+	fn undo_and_redo() {
+		let mut sim = Simulation::new(32, 32);
+		let mut history = History::new(usize::MAX);
+		assert!(!history.undo(&mut sim));
+
+		let empty = board(&sim);
+		history.record(&sim);
+		sim.create_part(EL_DUST.id, 5, 5);
+		sim.set_wall(8, 8, WallType::Wall);
+		let placed = board(&sim);
+		history.record(&sim);
+		sim.create_part(EL_DUST.id, 10, 10);
+		let last = board(&sim);
+
+		assert!(history.undo(&mut sim));
+		assert_eq!(board(&sim), placed);
+		assert!(history.undo(&mut sim));
+		assert_eq!(board(&sim), empty);
+		assert_eq!(sim.get_walls().get(8, 8), WallType::None);
+		assert!(!history.undo(&mut sim));
+
+		assert!(history.redo(&mut sim));
+		assert_eq!(board(&sim), placed);
+		assert_eq!(sim.get_walls().get(8, 8), WallType::Wall);
+		assert!(history.redo(&mut sim));
+		assert_eq!(board(&sim), last);
+		assert!(!history.redo(&mut sim));
+
+		// A new edit drops what could be redone
+		assert!(history.undo(&mut sim));
+		history.record(&sim);
+		assert!(!history.redo(&mut sim));
+		let used = history.undo.iter().map(Snapshot::size).sum::<usize>();
+		assert_eq!(history.used, used);
+	}
+
+	#[test]
+	fn stays_in_budget() {
+		let mut sim = Simulation::new(32, 32);
+		sim.create_part(EL_DUST.id, 5, 5);
+		let size = sim.snapshot().size();
+		let mut history = History::new(size * 2);
+
+		for _ in 0..5 {
+			history.record(&sim);
+		}
+		assert_eq!(history.undo.len(), 2);
+		assert_eq!(history.used, size * 2);
+
+		// Undone snapshots count too, redo gives them back
+		assert!(history.undo(&mut sim));
+		assert!(history.undo(&mut sim));
+		assert!(!history.undo(&mut sim));
+		assert!(history.used <= size * 2);
+		assert!(history.redo(&mut sim));
+		assert!(history.redo(&mut sim));
+		assert!(!history.redo(&mut sim));
+		assert_eq!(history.used, size * 2);
+	}
+}
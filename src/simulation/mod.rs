@@ -1,5 +1,24 @@
+pub mod air;
+pub mod automata;
+mod chunks;
+pub mod combustion;
+pub mod electricity;
 pub mod elements;
+pub mod energy;
+mod explosives;
+pub mod gravity;
+pub mod heat;
+pub mod history;
+pub mod ops;
 mod particle;
+pub mod properties;
+pub mod reactions;
+mod region;
+pub mod rng;
+pub mod save;
 pub mod sim;
+mod update_context;
+pub mod walls;
 
 pub use particle::Particle;
+pub use update_context::UpdateContext;
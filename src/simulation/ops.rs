@@ -0,0 +1,699 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::Read;
+
+use bzip2_rs::DecoderReader;
+
+use crate::simulation::air::CELL;
+use crate::simulation::gravity::GravityMode;
+use crate::simulation::heat::{MAX_TEMP, MIN_TEMP};
+use crate::simulation::properties::{PropKind, Property};
+use crate::simulation::save::LoadError;
+use crate::simulation::sim::{EdgeMode, Simulation, MAX_VELOCITY};
+use crate::simulation::walls::WallType;
+use crate::simulation::Particle;
+
+// Importer for OPS saves (.cps and .stm) of The Powder Toy
+// 12 byte header: "OPS1", TPT version, cell size, width and height in cells, size of the BSON data
+// followed by bzip2 compressed BSON, particles are in the binary "parts" and "partsPos" fields
+
+const MAGIC: [u8; 4] = *b"OPS1";
+const MAX_DATA: u32 = 1 << 28; // Bytes after decompression
+const MAX_DEPTH: usize = 16; // Nested BSON documents
+
+// Element ids of TPT before saves had a palette, only for elements PowderRS has
+const DEFAULT_IDS: [(u16, &str); 24] = [
+	(1, "DUST"),
+	(2, "WATR"),
+	(3, "OIL"),
+	(4, "FIRE"),
+	(7, "GUNP"),
+	(11, "PLEX"),
+	(13, "ICEI"),
+	(14, "METL"),
+	(15, "SPRK"),
+	(17, "WOOD"),
+	(18, "NEUT"),
+	(23, "WTRV"),
+	(26, "SALT"),
+	(27, "SLTW"),
+	(31, "PHOT"),
+	(38, "INSL"),
+	(43, "NTCT"),
+	(49, "PLSM"),
+	(53, "BTRY"),
+	(57, "SMKE"),
+	(67, "BRCK"),
+	(78, "LIFE"),
+	(136, "ELEC"),
+	(150, "NBHL")
+];
+
+// TPT identifiers that differ from the PowderRS name
+const RENAMED: [(&str, &str); 2] = [("ICEI", "ICE"), ("PLEX", "C4")];
+
+// Rules of TPT's LIFE element, picked by its ctype
+const LIFE_RULES: [(u32, &str); 3] = [(0, "GOL"), (1, "HLIF"), (12, "SEED")];
+
+/// What didn't make it into the simulation, counted per particle or wall cell
+#[derive(Default, Debug)]
+pub struct ImportReport {
+	pub elements:   BTreeMap<String, usize>, // TPT elements PowderRS doesn't have
+	pub properties: BTreeMap<String, usize>, // Set properties the element has no counterpart for
+	pub walls:      BTreeMap<u8, usize>,     // By TPT wall id
+	pub no_room:    usize // Outside of the board, in a wall or on top of another particle
+}
+
+impl ImportReport {
+	pub fn is_empty(&self) -> bool {
+		self.elements.is_empty() &&
+			self.properties.is_empty() &&
+			self.walls.is_empty() &&
+			self.no_room == 0
+	}
+}
+
+impl fmt::Display for ImportReport {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if self.is_empty() {
+			return write!(f, "Everything was imported");
+		}
+
+		let list = |counts: Vec<(String, usize)>| {
+			counts
+				.iter()
+				.map(|(name, count)| format!("{} ({})", name, count))
+				.collect::<Vec<_>>()
+				.join(", ")
+		};
+		let mut lines = Vec::new();
+		if !self.elements.is_empty() {
+			let counts = self.elements.iter().map(|(k, v)| (k.clone(), *v)).collect();
+			lines.push(format!("Dropped elements: {}", list(counts)));
+		}
+		if !self.properties.is_empty() {
+			let counts = self
+				.properties
+				.iter()
+				.map(|(k, v)| (k.clone(), *v))
+				.collect();
+			lines.push(format!("Dropped properties: {}", list(counts)));
+		}
+		if !self.walls.is_empty() {
+			let counts = self
+				.walls
+				.iter()
+				.map(|(k, v)| (format!("wall {}", k), *v))
+				.collect();
+			lines.push(format!("Dropped walls: {}", list(counts)));
+		}
+		if self.no_room > 0 {
+			lines.push(format!(
+				"Particles with no room on the board: {}",
+				self.no_room
+			));
+		}
+		write!(f, "{}", lines.join("\n"))
+	}
+}
+
+/// Particle fields of TPT, all of them are ints except temp and velocity
+struct OpsParticle {
+	p_type: u16,
+	x:      usize,
+	y:      usize,
+	temp:   f32,
+	vx:     f32,
+	vy:     f32,
+	fields: [(&'static str, u32); 8]
+}
+
+const FIELDS: [&str; 8] = [
+	"life", "tmp", "ctype", "dcolour", "tmp2", "pavg", "tmp3", "tmp4"
+];
+
+impl OpsParticle {
+	fn field(&self, name: &str) -> u32 {
+		self.fields
+			.iter()
+			.find(|(n, _)| *n == name)
+			.map_or(0, |(_, v)| *v)
+	}
+}
+
+impl Simulation {
+	/// Replaces the board with a TPT save, the board isn't touched if it returns an error
+	/// Elements are matched by name, everything that got left out is in the report
+	pub fn import_ops(&mut self, data: &[u8]) -> Result<ImportReport, LoadError> {
+		if data.len() < 12 || data[..4] != MAGIC {
+			return Err(LoadError::NotASave);
+		}
+		let cell = data[5] as usize;
+		let (cells_x, cells_y) = (data[6] as usize, data[7] as usize);
+		let size = u32::from_le_bytes([data[8], data[9], data[10], data[11]]);
+		if cell == 0 || size > MAX_DATA {
+			return Err(LoadError::Corrupted);
+		}
+
+		let mut bson = Vec::new();
+		DecoderReader::new(&data[12..])
+			.take(size as u64)
+			.read_to_end(&mut bson)
+			.map_err(|_| LoadError::Corrupted)?;
+		let root = read_document(&bson, 0)?;
+
+		let (width, height) = (cells_x * cell, cells_y * cell);
+		let parts = read_parts(&root, width, height)?;
+		let walls = match get(&root, "wallMap") {
+			Some(Bson::Binary(walls)) if walls.len() >= cells_x * cells_y => {
+				&walls[..cells_x * cells_y]
+			}
+			Some(_) => return Err(LoadError::Corrupted),
+			None => &[]
+		};
+		let palette = read_palette(&root);
+
+		let mut report = ImportReport::default();
+		self.replace_parts(&[]);
+		self.air.pressure.fill(0.0);
+		self.air.vx.fill(0.0);
+		self.air.vy.fill(0.0);
+		self.import_settings(&root);
+
+		for y in (0..self.get_height()).step_by(CELL) {
+			for x in (0..self.get_width()).step_by(CELL) {
+				self.set_wall(x, y, WallType::None);
+			}
+		}
+		for (i, &id) in walls.iter().enumerate() {
+			let Some(wall) = convert_wall(id)
+			else {
+				*report.walls.entry(id).or_default() += 1;
+				continue;
+			};
+			// TPT cells don't have to be the size of ours
+			let (x, y) = (i % cells_x * cell, i / cells_x * cell);
+			for wy in (y..y + cell).step_by(CELL) {
+				for wx in (x..x + cell).step_by(CELL) {
+					self.set_wall(wx, wy, wall);
+				}
+			}
+		}
+
+		for ops in parts {
+			let Some(mut pt) = self.convert_particle(&ops, &palette, &mut report)
+			else {
+				continue;
+			};
+			pt.x = ops.x as f32;
+			pt.y = ops.y as f32;
+			if self.add_part(pt).is_none() {
+				report.no_room += 1;
+			}
+		}
+		Ok(report)
+	}
+
+	fn import_settings(&mut self, root: &[(String, Bson)]) {
+		if let Some(mode) = get(root, "edgeMode").and_then(Bson::int) {
+			self.edge_mode = match mode {
+				1 => EdgeMode::Solid,
+				2 => EdgeMode::Wrap,
+				_ => EdgeMode::Void
+			};
+		}
+
+		let newtonian = matches!(get(root, "gravityEnable"), Some(Bson::Bool(true)));
+		if let Some(mode) = get(root, "gravityMode").and_then(Bson::int) {
+			self.gravity.mode = match mode {
+				_ if newtonian => GravityMode::Newtonian,
+				1 => GravityMode::Off,
+				2 => GravityMode::Radial,
+				_ => GravityMode::Vertical
+			};
+		}
+	}
+
+	/// Returns None if there's no counterpart for the element
+	fn convert_particle(
+		&self,
+		ops: &OpsParticle,
+		palette: &[(u16, String)],
+		report: &mut ImportReport
+	) -> Option<Particle> {
+		let tpt_name = tpt_name(palette, ops.p_type);
+		let name = if tpt_name == "LIFE" {
+			LIFE_RULES
+				.iter()
+				.find(|(ctype, _)| *ctype == ops.field("ctype"))
+				.map_or(format!("LIFE {}", ops.field("ctype")), |(_, name)| {
+					name.to_string()
+				})
+		}
+		else {
+			rename(&tpt_name).to_string()
+		};
+		let Some(el) = self.element_manager.get_element(&name)
+		else {
+			*report.elements.entry(name).or_default() += 1;
+			return None;
+		};
+
+		let mut pt = el.default.clone();
+		pt.temp = ops.temp.clamp(MIN_TEMP, MAX_TEMP);
+		pt.vx = ops.vx.clamp(-MAX_VELOCITY, MAX_VELOCITY);
+		pt.vy = ops.vy.clamp(-MAX_VELOCITY, MAX_VELOCITY);
+
+		for (field, value) in ops.fields {
+			// Life elements get their rule from the element, not from ctype
+			if value == 0 || (tpt_name == "LIFE" && field == "ctype") {
+				continue;
+			}
+
+			let converted = el
+				.properties
+				.iter()
+				.find(|prop| prop.name == field)
+				.and_then(|prop| Some((prop, self.convert_value(prop, value, palette)?)));
+			match converted {
+				Some((prop, value)) => prop.set(&mut pt, value),
+				None => *report.properties.entry(field.to_string()).or_default() += 1
+			}
+		}
+		Some(pt)
+	}
+
+	// Returns None if the value doesn't fit
+	fn convert_value(&self, prop: &Property, value: u32, palette: &[(u16, String)]) -> Option<u16> {
+		match prop.kind {
+			PropKind::Element => {
+				let name = rename(&tpt_name(palette, value.try_into().ok()?)).to_string();
+				Some(self.element_manager.get_element(&name)?.id)
+			}
+			PropKind::Bool => Some((value != 0) as u16),
+			_ => value.try_into().ok()
+		}
+	}
+}
+
+/// Reads particles in the order of their positions
+fn read_parts(
+	root: &[(String, Bson)],
+	width: usize,
+	height: usize
+) -> Result<Vec<OpsParticle>, LoadError> {
+	let (Some(Bson::Binary(data)), Some(Bson::Binary(positions))) =
+		(get(root, "parts"), get(root, "partsPos"))
+	else {
+		return Ok(Vec::new());
+	};
+	if positions.len() < width * height * 3 {
+		return Err(LoadError::Corrupted);
+	}
+
+	let mut r = PartsReader { data, pos: 0 };
+	let mut parts = Vec::new();
+	for (i, count) in positions.chunks_exact(3).take(width * height).enumerate() {
+		let count = (count[0] as usize) << 16 | (count[1] as usize) << 8 | count[2] as usize;
+		for _ in 0..count {
+			parts.push(r.particle(i % width, i / width)?);
+		}
+	}
+	Ok(parts)
+}
+
+struct PartsReader<'a> {
+	data: &'a [u8],
+	pos:  usize
+}
+
+impl PartsReader<'_> {
+	fn byte(&mut self) -> Result<u32, LoadError> {
+		let byte = *self.data.get(self.pos).ok_or(LoadError::Corrupted)?;
+		self.pos += 1;
+		Ok(byte as u32)
+	}
+
+	// Little endian
+	fn short(&mut self) -> Result<u32, LoadError> {
+		Ok(self.byte()? | self.byte()? << 8)
+	}
+
+	// Fields are only there if their bit in the descriptor is set, some have extra bytes with more bits
+	fn particle(&mut self, x: usize, y: usize) -> Result<OpsParticle, LoadError> {
+		let mut p_type = self.byte()?;
+		let mut desc = self.short()?;
+		if desc & 1 << 15 != 0 {
+			desc |= self.byte()? << 16;
+		}
+		if desc & 1 << 14 != 0 {
+			p_type |= self.byte()? << 8;
+		}
+
+		// Either an offset from 294.15 K or the whole temperature
+		let temp = if desc & 1 != 0 {
+			self.short()? as f32
+		}
+		else {
+			self.byte()? as u8 as i8 as f32 + 294.15
+		};
+
+		let mut fields = FIELDS.map(|name| (name, 0));
+		let mut set = |name: &str, value: u32| {
+			if let Some(field) = fields.iter_mut().find(|(n, _)| *n == name) {
+				field.1 = value;
+			}
+		};
+
+		if desc & 1 << 1 != 0 {
+			let mut life = self.byte()?;
+			if desc & 1 << 2 != 0 {
+				life |= self.byte()? << 8;
+			}
+			set("life", life);
+		}
+		if desc & 1 << 3 != 0 {
+			let mut tmp = self.byte()?;
+			if desc & 1 << 4 != 0 {
+				tmp |= self.byte()? << 8;
+				if desc & 1 << 12 != 0 {
+					tmp |= self.byte()? << 24;
+					tmp |= self.byte()? << 16;
+				}
+			}
+			set("tmp", tmp);
+		}
+		if desc & 1 << 5 != 0 {
+			let mut ctype = self.byte()?;
+			if desc & 1 << 9 != 0 {
+				ctype |= self.byte()? << 24;
+				ctype |= self.byte()? << 16;
+				ctype |= self.byte()? << 8;
+			}
+			set("ctype", ctype);
+		}
+		if desc & 1 << 6 != 0 {
+			let colour =
+				self.byte()? << 24 | self.byte()? << 16 | self.byte()? << 8 | self.byte()?;
+			set("dcolour", colour);
+		}
+
+		// Velocities are stored in 16ths of a pixel per tick, 127 being 0
+		let vx = if desc & 1 << 7 != 0 {
+			(self.byte()? as f32 - 127.0) / 16.0
+		}
+		else {
+			0.0
+		};
+		let vy = if desc & 1 << 8 != 0 {
+			(self.byte()? as f32 - 127.0) / 16.0
+		}
+		else {
+			0.0
+		};
+
+		if desc & 1 << 10 != 0 {
+			let mut tmp2 = self.byte()?;
+			if desc & 1 << 11 != 0 {
+				tmp2 |= self.byte()? << 8;
+			}
+			set("tmp2", tmp2);
+		}
+		if desc & 1 << 13 != 0 {
+			let pavg = self.short()? | self.short()? << 16;
+			set("pavg", pavg);
+		}
+		if desc & 1 << 16 != 0 {
+			let (mut tmp3, mut tmp4) = (self.short()?, self.short()?);
+			if desc & 1 << 17 != 0 {
+				tmp3 |= self.short()? << 16;
+				tmp4 |= self.short()? << 16;
+			}
+			set("tmp3", tmp3);
+			set("tmp4", tmp4);
+		}
+
+		Ok(OpsParticle {
+			p_type: p_type.try_into().map_err(|_| LoadError::Corrupted)?,
+			x,
+			y,
+			temp,
+			vx,
+			vy,
+			fields
+		})
+	}
+}
+
+/// Saved id to TPT identifier, newer saves list the ids they use
+fn read_palette(root: &[(String, Bson)]) -> Vec<(u16, String)> {
+	let Some(Bson::Document(palette)) = get(root, "palette")
+	else {
+		return DEFAULT_IDS
+			.iter()
+			.map(|(id, name)| (*id, name.to_string()))
+			.collect();
+	};
+
+	palette
+		.iter()
+		.filter_map(|(identifier, id)| {
+			let id = id.int()?.try_into().ok()?;
+			// Identifiers look like DEFAULT_PT_DUST
+			let name = identifier.rsplit("_PT_").next()?;
+			Some((id, name.to_string()))
+		})
+		.collect()
+}
+
+fn tpt_name(palette: &[(u16, String)], id: u16) -> String {
+	palette
+		.iter()
+		.find(|(saved, _)| *saved == id)
+		.map_or(format!("id {}", id), |(_, name)| name.clone())
+}
+
+fn rename(tpt_name: &str) -> &str {
+	RENAMED
+		.iter()
+		.find(|(tpt, _)| *tpt == tpt_name)
+		.map_or(tpt_name, |(_, name)| name)
+}
+
+fn convert_wall(id: u8) -> Option<WallType> {
+	let wall = match id {
+		0 => WallType::None,
+		1 => WallType::Conductor,
+		6 => WallType::AllowFluid,
+		8 => WallType::Wall,
+		9 => WallType::BlockParticles, // Lets air through
+		10 => WallType::AllowPowder,
+		13 => WallType::AllowGas,
+		16 => WallType::BlockAir,
+		_ => return None
+	};
+	Some(wall)
+}
+
+/// BSON values TPT uses, the rest is skipped
+enum Bson<'a> {
+	Double(f64),
+	Int(i64),
+	Bool(bool),
+	Binary(&'a [u8]),
+	Document(Vec<(String, Bson<'a>)>),
+	Other
+}
+
+impl Bson<'_> {
+	fn int(&self) -> Option<i64> {
+		match self {
+			Bson::Int(value) => Some(*value),
+			Bson::Double(value) => Some(*value as i64),
+			_ => None
+		}
+	}
+}
+
+fn get<'a, 'b>(document: &'b [(String, Bson<'a>)], key: &str) -> Option<&'b Bson<'a>> {
+	document.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+}
+
+/// Arrays are read as documents with keys "0", "1", ...
+fn read_document(data: &[u8], depth: usize) -> Result<Vec<(String, Bson<'_>)>, LoadError> {
+	let len = data
+		.get(..4)
+		.map(|len| i32::from_le_bytes(len.try_into().unwrap()))
+		.filter(|len| *len >= 5 && *len as usize <= data.len() && depth < MAX_DEPTH)
+		.ok_or(LoadError::Corrupted)? as usize;
+	let data = &data[..len];
+
+	let mut entries = Vec::new();
+	let mut pos = 4;
+	loop {
+		let kind = *data.get(pos).ok_or(LoadError::Corrupted)?;
+		pos += 1;
+		if kind == 0 {
+			break;
+		}
+
+		let key_len = data[pos..]
+			.iter()
+			.position(|b| *b == 0)
+			.ok_or(LoadError::Corrupted)?;
+		let key = String::from_utf8_lossy(&data[pos..pos + key_len]).into_owned();
+		pos += key_len + 1;
+
+		let rest = data.get(pos..).ok_or(LoadError::Corrupted)?;
+		let int = |bytes: usize| {
+			rest.get(..bytes)
+				.map(|b| b.iter().rev().fold(0i64, |acc, b| acc << 8 | *b as i64))
+				.ok_or(LoadError::Corrupted)
+		};
+		// Lengths are signed, a negative one means the save is corrupted
+		let length = |extra: usize| {
+			usize::try_from(int(4)? as i32)
+				.ok()
+				.and_then(|len| len.checked_add(extra))
+				.ok_or(LoadError::Corrupted)
+		};
+		let (value, size) = match kind {
+			0x01 => (Bson::Double(f64::from_bits(int(8)? as u64)), 8),
+			0x02 | 0x0D => (Bson::Other, length(4)?), // String and code
+			0x03 | 0x04 => {
+				let size = length(0)?;
+				(Bson::Document(read_document(rest, depth + 1)?), size)
+			}
+			0x05 => {
+				let size = length(5)?;
+				let binary = rest.get(5..size).ok_or(LoadError::Corrupted)?;
+				(Bson::Binary(binary), size)
+			}
+			0x07 => (Bson::Other, 12), // Object id
+			0x08 => (Bson::Bool(int(1)? != 0), 1),
+			0x09 | 0x11 => (Bson::Other, 8), // Date and timestamp
+			0x0A => (Bson::Other, 0),        // Null
+			0x10 => (Bson::Int(int(4)? as i32 as i64), 4),
+			0x12 => (Bson::Int(int(8)?), 8),
+			_ => return Err(LoadError::Corrupted)
+		};
+		if size > rest.len() {
+			return Err(LoadError::Corrupted);
+		}
+		pos += size;
+		entries.push((key, value));
+	}
+	Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn element(kind: u8, key: &str, value: &[u8]) -> Vec<u8> {
+		let mut data = vec![kind];
+		data.extend(key.as_bytes());
+		data.push(0);
+		data.extend(value);
+		data
+	}
+
+	fn document(elements: &[Vec<u8>]) -> Vec<u8> {
+		let body = elements.concat();
+		let mut data = ((body.len() + 5) as i32).to_le_bytes().to_vec();
+		data.extend(body);
+		data.push(0);
+		data
+	}
+
+	fn binary(value: &[u8]) -> Vec<u8> {
+		let mut data = (value.len() as i32).to_le_bytes().to_vec();
+		data.push(0); // Subtype
+		data.extend(value);
+		data
+	}
+
+	#[test]
+	fn reads_documents() {
+		let inner = document(&[element(0x10, "0", &7i32.to_le_bytes())]);
+		let mut string = 3i32.to_le_bytes().to_vec();
+		string.extend(b"ab\0");
+		let data = document(&[
+			element(0x10, "int", &(-5i32).to_le_bytes()),
+			element(0x01, "double", &2.5f64.to_le_bytes()),
+			element(0x08, "bool", &[1]),
+			element(0x02, "string", &string),
+			element(0x05, "binary", &binary(&[1, 2, 3])),
+			element(0x04, "array", &inner)
+		]);
+
+		let root = read_document(&data, 0).unwrap();
+		assert_eq!(get(&root, "int").and_then(Bson::int), Some(-5));
+		assert_eq!(get(&root, "double").and_then(Bson::int), Some(2));
+		assert!(matches!(get(&root, "bool"), Some(Bson::Bool(true))));
+		assert!(matches!(get(&root, "string"), Some(Bson::Other)));
+		assert!(matches!(
+			get(&root, "binary"),
+			Some(Bson::Binary([1, 2, 3]))
+		));
+		let Some(Bson::Document(array)) = get(&root, "array")
+		else {
+			panic!("Array wasn't read");
+		};
+		assert_eq!(get(array, "0").and_then(Bson::int), Some(7));
+	}
+
+	#[test]
+	fn rejects_negative_lengths() {
+		let mut string = (-8i32).to_le_bytes().to_vec();
+		string.extend(b"ab\0");
+		let data = document(&[element(0x02, "string", &string)]);
+		assert_eq!(read_document(&data, 0).err(), Some(LoadError::Corrupted));
+
+		let mut binary = (-5i32).to_le_bytes().to_vec();
+		binary.extend([0, 1, 2, 3]);
+		let data = document(&[element(0x05, "binary", &binary)]);
+		assert_eq!(read_document(&data, 0).err(), Some(LoadError::Corrupted));
+
+		let mut data = document(&[]);
+		data[..4].copy_from_slice(&(-1i32).to_le_bytes());
+		assert_eq!(read_document(&data, 0).err(), Some(LoadError::Corrupted));
+	}
+
+	#[test]
+	fn reads_particles() {
+		// Dust with a full temperature and both velocities, in the second of two cells
+		let parts = [1, 0x81, 0x01, 44, 1, 159, 111];
+		let data = document(&[
+			element(0x05, "parts", &binary(&parts)),
+			element(0x05, "partsPos", &binary(&[0, 0, 0, 0, 0, 1]))
+		]);
+
+		let root = read_document(&data, 0).unwrap();
+		let parts = read_parts(&root, 2, 1).unwrap();
+		assert_eq!(parts.len(), 1);
+		let pt = &parts[0];
+		assert_eq!((pt.p_type, pt.x, pt.y), (1, 1, 0));
+		assert_eq!(pt.temp, 300.0);
+		assert_eq!((pt.vx, pt.vy), (2.0, -1.0));
+		assert_eq!(tpt_name(&read_palette(&root), pt.p_type), "DUST");
+
+		// Missing bytes of the particle
+		let data = document(&[
+			element(0x05, "parts", &binary(&[1, 0x81, 0x01, 44])),
+			element(0x05, "partsPos", &binary(&[0, 0, 1]))
+		]);
+		let root = read_document(&data, 0).unwrap();
+		assert!(matches!(read_parts(&root, 1, 1), Err(LoadError::Corrupted)));
+	}
+
+	#[test]
+	fn rejects_other_files() {
+		let mut sim = Simulation::new(32, 32);
+		assert_eq!(sim.import_ops(b"PWRS").err(), Some(LoadError::NotASave));
+		assert_eq!(
+			sim.import_ops(b"OPS1\x60\x00\x01\x01\0\0\0\0").err(),
+			Some(LoadError::Corrupted)
+		);
+	}
+}
@@ -1,16 +1,18 @@
 use crate::simulation::elements::{Element, ElementManager, EL_NONE};
+use crate::simulation::heat::R_TEMP;
 
 #[repr(C)]
 #[derive(Debug, Clone)]
 pub struct Particle {
 	pub p_type: u16, // p_ because "type" is a keyword
-	pub prop1:  u16, /* Dummy properties :P TODO: Come up with property names that make sense (impossible) */
+	pub prop1:  u16, // Meaning depends on the element, see `Element::properties`
 	pub prop2:  u16,
 	pub prop3:  u16,
 	pub x:      f32,
 	pub y:      f32,
 	pub vx:     f32,
-	pub vy:     f32
+	pub vy:     f32,
+	pub temp:   f32 // Kelvin
 }
 
 impl Particle {
@@ -28,6 +30,11 @@ impl Particle {
 		self
 	}
 
+	pub const fn with_temp(mut self, temp: f32) -> Self {
+		self.temp = temp;
+		self
+	}
+
 	pub fn get_type<'a>(&'a self, elements: &'a ElementManager) -> &Element {
 		elements
 			.elements
@@ -45,7 +52,8 @@ impl Particle {
 			x:      0f32,
 			y:      0f32,
 			vx:     0f32,
-			vy:     0f32
+			vy:     0f32,
+			temp:   R_TEMP
 		}
 	}
 }
@@ -0,0 +1,235 @@
+use crate::simulation::elements::ElementManager;
+use crate::simulation::heat::{MAX_TEMP, MIN_TEMP};
+use crate::simulation::sim::{ParticleHandle, Simulation};
+use crate::simulation::Particle;
+
+/// Field of `Particle` a property is stored in
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PropSlot {
+	Prop1,
+	Prop2,
+	Prop3
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PropKind {
+	Int,
+	Element, // Element id
+	Bool,
+	Float // Built in properties only, slots can't hold floats
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PropValue {
+	Int(u16),
+	Element(u16),
+	Bool(bool),
+	Float(f32)
+}
+
+impl PropValue {
+	pub fn kind(&self) -> PropKind {
+		match self {
+			PropValue::Int(_) => PropKind::Int,
+			PropValue::Element(_) => PropKind::Element,
+			PropValue::Bool(_) => PropKind::Bool,
+			PropValue::Float(_) => PropKind::Float
+		}
+	}
+}
+
+/// Named property an element stores in one of the particle's slots
+#[derive(Copy, Clone, Debug)]
+pub struct Property {
+	pub name: &'static str,
+	pub slot: PropSlot,
+	pub kind: PropKind
+}
+
+// Properties shared by many elements, elements can declare their own too
+pub const CTYPE: Property = Property {
+	name: "ctype",
+	slot: PropSlot::Prop1,
+	kind: PropKind::Element
+};
+pub const LIFE: Property = Property {
+	name: "life",
+	slot: PropSlot::Prop2,
+	kind: PropKind::Int
+};
+pub const TMP: Property = Property {
+	name: "tmp",
+	slot: PropSlot::Prop3,
+	kind: PropKind::Int
+};
+
+// Fields every particle has
+const BUILT_IN: [&str; 6] = ["type", "x", "y", "vx", "vy", "temp"];
+
+impl Property {
+	/// Raw value of the slot
+	pub fn get(&self, pt: &Particle) -> u16 {
+		match self.slot {
+			PropSlot::Prop1 => pt.prop1,
+			PropSlot::Prop2 => pt.prop2,
+			PropSlot::Prop3 => pt.prop3
+		}
+	}
+
+	pub fn set(&self, pt: &mut Particle, value: u16) {
+		match self.slot {
+			PropSlot::Prop1 => pt.prop1 = value,
+			PropSlot::Prop2 => pt.prop2 = value,
+			PropSlot::Prop3 => pt.prop3 = value
+		}
+	}
+
+	fn value(&self, pt: &Particle) -> PropValue {
+		let raw = self.get(pt);
+		match self.kind {
+			PropKind::Int => PropValue::Int(raw),
+			PropKind::Element => PropValue::Element(raw),
+			PropKind::Bool => PropValue::Bool(raw != 0),
+			PropKind::Float => PropValue::Float(raw as f32) // Shouldn't be declared
+		}
+	}
+}
+
+impl Particle {
+	/// Built in properties followed by the ones declared by the particle's element
+	pub fn properties(&self, elements: &ElementManager) -> Vec<(&'static str, PropValue)> {
+		let declared = self.get_type(elements).properties;
+
+		BUILT_IN
+			.into_iter()
+			.chain(declared.iter().map(|prop| prop.name))
+			.filter_map(|name| Some((name, self.get_property(name, elements)?)))
+			.collect()
+	}
+
+	/// Returns None if the particle has no property with that name
+	pub fn get_property(&self, name: &str, elements: &ElementManager) -> Option<PropValue> {
+		let value = match name {
+			"type" => PropValue::Element(self.p_type),
+			"x" => PropValue::Float(self.x),
+			"y" => PropValue::Float(self.y),
+			"vx" => PropValue::Float(self.vx),
+			"vy" => PropValue::Float(self.vy),
+			"temp" => PropValue::Float(self.temp),
+			_ => self.find_property(name, elements)?.value(self)
+		};
+		Some(value)
+	}
+
+	/// Returns false if there's no such property, or the value is of the wrong kind or invalid
+	/// Changing type or position of a particle in a simulation breaks pmap, use `Simulation::set_property`
+	pub fn set_property(
+		&mut self,
+		name: &str,
+		value: PropValue,
+		elements: &ElementManager
+	) -> bool {
+		match value {
+			PropValue::Element(id) if elements.elements.get(id as usize).is_none() => return false,
+			PropValue::Float(value) if !value.is_finite() => return false,
+			_ => {}
+		}
+
+		match (name, value) {
+			("type", PropValue::Element(id)) => self.p_type = id,
+			("x", PropValue::Float(x)) => self.x = x,
+			("y", PropValue::Float(y)) => self.y = y,
+			("vx", PropValue::Float(vx)) => self.vx = vx,
+			("vy", PropValue::Float(vy)) => self.vy = vy,
+			("temp", PropValue::Float(temp)) => self.temp = temp.clamp(MIN_TEMP, MAX_TEMP),
+			_ if BUILT_IN.contains(&name) => return false,
+			_ => {
+				let Some(prop) = self
+					.find_property(name, elements)
+					.filter(|prop| prop.kind == value.kind())
+				else {
+					return false;
+				};
+
+				let raw = match value {
+					PropValue::Int(raw) | PropValue::Element(raw) => raw,
+					PropValue::Bool(value) => value as u16,
+					PropValue::Float(_) => return false
+				};
+				prop.set(self, raw);
+			}
+		}
+		true
+	}
+
+	fn find_property(&self, name: &str, elements: &ElementManager) -> Option<Property> {
+		self.get_type(elements)
+			.properties
+			.iter()
+			.find(|prop| prop.name == name)
+			.copied()
+	}
+}
+
+impl Simulation {
+	/// Same as `Particle::set_property`, but keeps pmap in sync
+	/// Moving only works onto an empty spot on the board and changing type to 0 kills the particle
+	pub fn set_property(&mut self, id: ParticleHandle, name: &str, value: PropValue) -> bool {
+		if self.parts.get(id).filter(|pt| pt.p_type != 0).is_none() {
+			return false;
+		}
+
+		match (name, value) {
+			("type", PropValue::Element(p_type)) => {
+				if self.element_manager.elements.get(p_type as usize).is_none() {
+					return false;
+				}
+				self.change_type(id, p_type);
+				true
+			}
+			("x" | "y", PropValue::Float(value)) => {
+				let pt = &self.parts[id];
+				let (x, y) = if name == "x" {
+					(value, pt.y)
+				}
+				else {
+					(pt.x, value)
+				};
+				let on_board = (0.0..self.get_width() as f32).contains(&x) &&
+					(0.0..self.get_height() as f32).contains(&y);
+				if !on_board {
+					return false;
+				}
+
+				// Energy particles live in their own map
+				let energy = self.is_energy(id);
+				let taken = if energy {
+					self.get_photon(x as usize, y as usize)
+				}
+				else {
+					self.get_id(x as usize, y as usize)
+				};
+				if taken.is_some_and(|other| other != id) {
+					return false;
+				}
+
+				let old = pt.x as usize + pt.y as usize * self.get_width();
+				let new = x as usize + y as usize * self.get_width();
+				let map = if energy {
+					&mut self.photons
+				}
+				else {
+					&mut self.pmap
+				};
+				if map[old] == Some(id) {
+					map[old] = None;
+				}
+				map[new] = Some(id);
+				self.parts[id].x = x;
+				self.parts[id].y = y;
+				true
+			}
+			_ => self.parts[id].set_property(name, value, &self.element_manager)
+		}
+	}
+}
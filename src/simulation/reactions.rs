@@ -0,0 +1,95 @@
+use rand::Rng;
+
+use crate::simulation::elements::*;
+use crate::simulation::region::Region;
+use crate::simulation::sim::ParticleHandle;
+
+/// `a` touching `b` turns them into `into_a` and `into_b`, type 0 kills the particle
+/// `probability` is the chance per tick for every touching pair
+/// `temp_range` is inclusive and checked against the average temperature of both particles
+#[derive(Copy, Clone)]
+pub struct Reaction {
+	pub a:           u16,
+	pub b:           u16,
+	pub into_a:      u16,
+	pub into_b:      u16,
+	pub probability: f32,
+	pub temp_range:  Option<(f32, f32)>
+}
+
+pub const REACTIONS: &[Reaction] = &[
+	Reaction {
+		a:           EL_WATR.id,
+		b:           EL_SALT.id,
+		into_a:      EL_SLTW.id,
+		into_b:      EL_NONE.id,
+		probability: 0.05,
+		temp_range:  None
+	},
+	// Water puts out fire
+	Reaction {
+		a:           EL_FIRE.id,
+		b:           EL_WATR.id,
+		into_a:      EL_NONE.id,
+		into_b:      EL_WTRV.id,
+		probability: 0.5,
+		temp_range:  None
+	}
+];
+
+impl Region<'_> {
+	/// Checks reactions of the particle with all its neighbours, at most one reaction happens per tick
+	/// Returns true if the particle was killed
+	pub(super) fn apply_reactions(&mut self, pt_id: ParticleHandle) -> bool {
+		let pt = &self.parts[pt_id];
+		if self.element_manager.get_reactions(pt.p_type).is_empty() {
+			return false;
+		}
+
+		let (x, y) = (pt.x as isize, pt.y as isize);
+		for (dx, dy) in [
+			(-1, -1),
+			(0, -1),
+			(1, -1),
+			(-1, 0),
+			(1, 0),
+			(-1, 1),
+			(0, 1),
+			(1, 1)
+		] {
+			let Some(other_id) = self.get_id_wrapped(x + dx, y + dy)
+			else {
+				continue;
+			};
+			// Boards smaller than 3 pixels wrap around to the particle itself
+			if other_id == pt_id {
+				continue;
+			}
+
+			let (pt, other) = (&self.parts[pt_id], &self.parts[other_id]);
+			let temp = (pt.temp + other.temp) / 2.0;
+
+			let reaction = self
+				.element_manager
+				.get_reactions(pt.p_type)
+				.iter()
+				.find(|r| {
+					r.b == other.p_type &&
+						r.temp_range
+							.iter()
+							.all(|(min, max)| (*min..=*max).contains(&temp))
+				})
+				.copied();
+
+			if let Some(reaction) = reaction {
+				if !self.rng().gen_bool(reaction.probability as f64) {
+					continue;
+				}
+
+				self.change_type(other_id, reaction.into_b);
+				return self.change_type(pt_id, reaction.into_a);
+			}
+		}
+		false
+	}
+}
@@ -0,0 +1,502 @@
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::simulation::air::Air;
+use crate::simulation::chunks::{Chunk, CHUNK_HEIGHT, REACH};
+use crate::simulation::elements::*;
+use crate::simulation::explosives::Blast;
+use crate::simulation::gravity::Gravity;
+use crate::simulation::rng::SimRng;
+use crate::simulation::sim::{EdgeMode, ParticleHandle, Simulation};
+use crate::simulation::walls::Walls;
+use crate::simulation::Particle;
+
+const NO_CHUNK: u32 = u32::MAX; // Row that no chunk of the pass owns
+
+/// Part of the board that particle updates work on, either the whole board or one chunk of a pass
+/// Chunks of a pass run at the same time, so a chunk only gets the rows around it and the particles in them
+/// Anything else is treated as out of reach, indexing `parts`, `pmap` or `photons` outside of it panics
+pub(super) struct Region<'a> {
+	pub(super) parts:           Parts<'a>,
+	pub(super) pmap:            PosMap<'a>,
+	pub(super) photons:         PosMap<'a>,
+	pub(super) air:             &'a Air,
+	pub(super) gravity:         &'a Gravity,
+	pub(super) element_manager: &'a ElementManager,
+	pub(super) edge_mode:       EdgeMode,
+	pub(super) width:           usize,
+	pub(super) height:          usize,
+
+	tick:  u64,
+	scope: Scope<'a>
+}
+
+enum Scope<'a> {
+	// Outside of the particle update, nothing else runs at the same time
+	Board {
+		free_list:  &'a mut Vec<ParticleHandle>,
+		part_count: &'a mut usize,
+		parts_end:  &'a mut usize,
+		rng:        &'a mut SimRng,
+		blasts:     &'a mut Vec<Blast>,
+		walls:      &'a mut Walls
+	},
+	// Ids come from the chunk's part of the free list, walls are sparked once the pass is done
+	Chunk {
+		chunk:     &'a mut Chunk,
+		free_list: &'a [ParticleHandle],
+		walls:     &'a Walls
+	}
+}
+
+// SAFETY: Regions of a pass share the particle and position arrays,
+// but every access goes through `Parts` and `PosMap` which only allow what the region owns
+unsafe impl Send for Region<'_> {}
+
+/// Slice that several regions of a pass point into at once
+struct Shared<'a, T> {
+	ptr:     *mut T,
+	len:     usize,
+	_marker: PhantomData<&'a mut [T]>
+}
+
+impl<'a, T> Shared<'a, T> {
+	fn new(slice: &'a mut [T]) -> Self {
+		Self {
+			ptr:     slice.as_mut_ptr(),
+			len:     slice.len(),
+			_marker: PhantomData
+		}
+	}
+}
+
+impl<T> Clone for Shared<'_, T> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<T> Copy for Shared<'_, T> {}
+
+/// Particles of a region, in a chunk only the ones whose owner is the chunk's tag can be used
+/// Owners are set before every pass by the rows particles are in and by the chunk that creates a particle
+pub(super) struct Parts<'a> {
+	data:   Shared<'a, Particle>,
+	owners: Option<(&'a [AtomicU64], u64)> // Owner of every particle and this chunk's tag
+}
+
+impl Parts<'_> {
+	pub(super) fn owns(&self, id: ParticleHandle) -> bool {
+		if id >= self.data.len {
+			return false;
+		}
+
+		match self.owners {
+			Some((owners, tag)) => owners[id].load(Ordering::Relaxed) == tag,
+			None => true
+		}
+	}
+
+	pub(super) fn get(&self, id: ParticleHandle) -> Option<&Particle> {
+		// SAFETY: Owned particles aren't used by any other region
+		self.owns(id).then(|| unsafe { &*self.data.ptr.add(id) })
+	}
+
+	pub(super) fn get_mut(&mut self, id: ParticleHandle) -> Option<&mut Particle> {
+		// SAFETY: Same as `get`, `&mut self` keeps this the only reference from this region
+		self.owns(id)
+			.then(|| unsafe { &mut *self.data.ptr.add(id) })
+	}
+
+	// Free ids are only given to one chunk, so no one else can own them
+	fn claim(&self, id: ParticleHandle) {
+		if let Some((owners, tag)) = self.owners {
+			owners[id].store(tag, Ordering::Relaxed);
+		}
+	}
+}
+
+impl Index<ParticleHandle> for Parts<'_> {
+	type Output = Particle;
+
+	fn index(&self, id: ParticleHandle) -> &Particle {
+		self.get(id).expect("Particle is out of reach")
+	}
+}
+
+impl IndexMut<ParticleHandle> for Parts<'_> {
+	fn index_mut(&mut self, id: ParticleHandle) -> &mut Particle {
+		self.get_mut(id).expect("Particle is out of reach")
+	}
+}
+
+/// Ids by position, in a chunk only the rows the chunk owns can be used
+pub(super) struct PosMap<'a> {
+	data:  Shared<'a, Option<ParticleHandle>>,
+	width: usize,
+	rows:  Option<(&'a [u32], u32)> // Owner of every row and this chunk
+}
+
+impl PosMap<'_> {
+	pub(super) fn owns(&self, pos: usize) -> bool {
+		if pos >= self.data.len {
+			return false;
+		}
+
+		match self.rows {
+			Some((rows, chunk)) => rows[pos / self.width] == chunk,
+			None => true
+		}
+	}
+}
+
+impl Index<usize> for PosMap<'_> {
+	type Output = Option<ParticleHandle>;
+
+	fn index(&self, pos: usize) -> &Option<ParticleHandle> {
+		assert!(self.owns(pos), "Position is out of reach");
+		// SAFETY: Rows are owned by at most one region
+		unsafe { &*self.data.ptr.add(pos) }
+	}
+}
+
+impl IndexMut<usize> for PosMap<'_> {
+	fn index_mut(&mut self, pos: usize) -> &mut Option<ParticleHandle> {
+		assert!(self.owns(pos), "Position is out of reach");
+		// SAFETY: Same as `index`
+		unsafe { &mut *self.data.ptr.add(pos) }
+	}
+}
+
+impl Simulation {
+	/// Whole board, for everything outside of the particle update
+	pub(super) fn region(&mut self) -> Region<'_> {
+		let (width, height) = (self.get_width(), self.get_height());
+		Region {
+			parts: Parts {
+				data:   Shared::new(&mut self.parts[..]),
+				owners: None
+			},
+			pmap: PosMap {
+				data: Shared::new(&mut self.pmap[..]),
+				width,
+				rows: None
+			},
+			photons: PosMap {
+				data: Shared::new(&mut self.photons[..]),
+				width,
+				rows: None
+			},
+			air: &self.air,
+			gravity: &self.gravity,
+			element_manager: &self.element_manager,
+			edge_mode: self.edge_mode,
+			width,
+			height,
+			tick: self.tick,
+			scope: Scope::Board {
+				free_list:  &mut self.free_list,
+				part_count: &mut self.part_count,
+				parts_end:  &mut self.parts_end,
+				rng:        &mut self.rng,
+				blasts:     &mut self.blasts,
+				walls:      &mut self.walls
+			}
+		}
+	}
+
+	/// Regions of the chunks updated in the pass, each one owns its rows and the `REACH` rows around them
+	/// They never share a row or a particle, so they can run on separate threads
+	/// `rows` is filled with the chunk owning every row
+	pub(super) fn chunk_regions<'a>(
+		&'a mut self,
+		pass: usize,
+		rows: &'a mut Vec<u32>
+	) -> Vec<Region<'a>> {
+		let count = self.chunks.len();
+		let (width, height) = (self.get_width(), self.get_height());
+		let in_pass: Vec<bool> = (0..count).map(|c| self.chunk_pass(c) == pass).collect();
+
+		rows.clear();
+		rows.resize(height, NO_CHUNK);
+		for chunk in (0..count).filter(|&c| in_pass[c]) {
+			// Leftover rows go to the last chunk
+			let top = chunk * CHUNK_HEIGHT;
+			let bottom = if chunk == count - 1 {
+				height
+			}
+			else {
+				top + CHUNK_HEIGHT
+			};
+
+			for y in top as isize - REACH as isize..(bottom + REACH) as isize {
+				let row = if self.edge_mode == EdgeMode::Wrap {
+					y.rem_euclid(height as isize) as usize
+				}
+				else if (0..height as isize).contains(&y) {
+					y as usize
+				}
+				else {
+					continue;
+				};
+				// First one wins, so a row never has two owners even on tiny wrapping boards
+				if rows[row] == NO_CHUNK {
+					rows[row] = chunk as u32;
+				}
+			}
+		}
+
+		// Tags only ever grow, so owners left from earlier passes never match
+		let first_tag = self.owner_tag;
+		self.owner_tag += count as u64;
+		let end = self
+			.chunks
+			.iter()
+			.map(|c| c.parts_end)
+			.fold(self.parts_end, usize::max);
+		for id in 0..end {
+			let pt = &self.parts[id];
+			let chunk = rows.get(pt.y as usize).copied().unwrap_or(NO_CHUNK);
+			if pt.p_type != 0 && chunk != NO_CHUNK {
+				self.owners[id].store(first_tag + chunk as u64, Ordering::Relaxed);
+			}
+		}
+
+		let rows: &'a [u32] = rows;
+		let parts = Shared::new(&mut self.parts[..]);
+		let pmap = Shared::new(&mut self.pmap[..]);
+		let photons = Shared::new(&mut self.photons[..]);
+		let (owners, free_list, walls) = (&*self.owners, &*self.free_list, &self.walls);
+		let (air, gravity, element_manager) = (&self.air, &self.gravity, &self.element_manager);
+		let (edge_mode, tick) = (self.edge_mode, self.tick);
+
+		self.chunks
+			.iter_mut()
+			.enumerate()
+			.filter(|(c, _)| in_pass[*c])
+			.map(|(c, chunk)| Region {
+				parts: Parts {
+					data:   parts,
+					owners: Some((owners, first_tag + c as u64))
+				},
+				pmap: PosMap {
+					data: pmap,
+					width,
+					rows: Some((rows, c as u32))
+				},
+				photons: PosMap {
+					data: photons,
+					width,
+					rows: Some((rows, c as u32))
+				},
+				air,
+				gravity,
+				element_manager,
+				edge_mode,
+				width,
+				height,
+				tick,
+				scope: Scope::Chunk {
+					chunk,
+					free_list,
+					walls
+				}
+			})
+			.collect()
+	}
+}
+
+impl Region<'_> {
+	pub(super) fn get_tick(&self) -> u64 {
+		self.tick
+	}
+
+	/// Every chunk has its own RNG, so the result doesn't depend on the order chunks run in
+	pub(super) fn rng(&mut self) -> &mut SimRng {
+		match &mut self.scope {
+			Scope::Board { rng, .. } => rng,
+			Scope::Chunk { chunk, .. } => &mut chunk.rng
+		}
+	}
+
+	pub(super) fn walls(&self) -> &Walls {
+		match &self.scope {
+			Scope::Board { walls, .. } => walls,
+			Scope::Chunk { walls, .. } => walls
+		}
+	}
+
+	/// Particles that were in the chunk at the start of the tick, none for the whole board
+	pub(super) fn chunk_parts(&self) -> &[ParticleHandle] {
+		match &self.scope {
+			Scope::Board { .. } => &[],
+			Scope::Chunk { chunk, .. } => &chunk.parts
+		}
+	}
+
+	/// True if the position is on the board and in a row the region owns
+	pub(super) fn in_reach(&self, x: usize, y: usize) -> bool {
+		x < self.width && y < self.height && self.pmap.owns(x + y * self.width)
+	}
+
+	/// Same as `Simulation::wrap_pos`, the position can still be out of reach
+	pub(super) fn wrap_pos(&self, x: isize, y: isize) -> Option<(usize, usize)> {
+		let (w, h) = (self.width as isize, self.height as isize);
+		if self.edge_mode == EdgeMode::Wrap {
+			return Some((x.rem_euclid(w) as usize, y.rem_euclid(h) as usize));
+		}
+
+		if x < 0 || y < 0 || x >= w || y >= h {
+			return None;
+		}
+		Some((x as usize, y as usize))
+	}
+
+	/// Returns None for positions out of reach too
+	pub(super) fn get_id(&self, x: usize, y: usize) -> Option<ParticleHandle> {
+		if !self.in_reach(x, y) {
+			return None;
+		}
+
+		self.pmap[x + y * self.width]
+	}
+
+	pub(super) fn get_id_wrapped(&self, x: isize, y: isize) -> Option<ParticleHandle> {
+		let (x, y) = self.wrap_pos(x, y)?;
+		self.get_id(x, y)
+	}
+
+	/// Fails outside of the board, out of reach or if the spot is taken
+	pub(super) fn add_part(&mut self, part: Particle) -> Option<ParticleHandle> {
+		if part.p_type == 0 {
+			return None;
+		}
+		if part.x < 0.0 || part.y < 0.0 || !self.in_reach(part.x as usize, part.y as usize) {
+			return None;
+		}
+		let behaviour = part.get_type(self.element_manager).behaviour;
+		let energy = matches!(behaviour, ElementBehaviour::Energy);
+		let pos = part.x as usize + part.y as usize * self.width;
+		let taken = if energy {
+			self.photons[pos]
+		}
+		else {
+			self.pmap[pos]
+		};
+		if taken.is_some() ||
+			self.walls()
+				.get(part.x as usize, part.y as usize)
+				.blocks(behaviour)
+		{
+			return None;
+		}
+
+		let i = self.alloc()?;
+		if energy {
+			self.photons[pos] = Some(i);
+		}
+		else {
+			self.pmap[pos] = Some(i);
+		}
+		self.parts[i] = part;
+		Some(i)
+	}
+
+	pub(super) fn create_part(
+		&mut self,
+		p_type: u16,
+		x: usize,
+		y: usize
+	) -> Option<ParticleHandle> {
+		let mut part = self
+			.element_manager
+			.elements
+			.get(p_type as usize)?
+			.default
+			.clone();
+		part.x = x as f32;
+		part.y = y as f32;
+		self.add_part(part)
+	}
+
+	fn alloc(&mut self) -> Option<ParticleHandle> {
+		match &mut self.scope {
+			Scope::Board {
+				free_list,
+				part_count,
+				parts_end,
+				..
+			} => {
+				let i = free_list.pop()?;
+				**part_count += 1;
+				**parts_end = (**parts_end).max(i + 1);
+				Some(i)
+			}
+			Scope::Chunk {
+				chunk, free_list, ..
+			} => {
+				let i = chunk.alloc(free_list)?;
+				self.parts.claim(i);
+				Some(i)
+			}
+		}
+	}
+
+	/// Fails if the particle isn't alive or is out of reach
+	pub(super) fn kill_part(&mut self, id: ParticleHandle) -> Result<(), ()> {
+		if self.parts.get(id).filter(|pt| pt.p_type != 0).is_none() {
+			return Err(());
+		}
+
+		let pos = self.parts[id].x as usize + (self.parts[id].y as usize * self.width);
+		if !self.is_energy(id) {
+			self.pmap[pos] = None;
+		}
+		else if self.photons[pos] == Some(id) {
+			self.photons[pos] = None;
+		}
+		self.free_part(id);
+		Ok(())
+	}
+
+	// Doesn't touch pmap
+	pub(super) fn free_part(&mut self, id: ParticleHandle) {
+		self.parts[id] = Particle::default();
+		match &mut self.scope {
+			Scope::Board {
+				free_list,
+				part_count,
+				parts_end,
+				..
+			} => {
+				**part_count -= 1;
+				free_list.push(id);
+				while **parts_end > 0 && self.parts[**parts_end - 1].p_type == 0 {
+					**parts_end -= 1;
+				}
+			}
+			Scope::Chunk { chunk, .. } => chunk.freed.push(id)
+		}
+	}
+
+	/// Sparks the conductor wall at x, y, in a chunk this happens once the pass is done
+	/// Returns false if there's no conductor wall there
+	pub(super) fn spark_wall(&mut self, x: usize, y: usize) -> bool {
+		match &mut self.scope {
+			Scope::Board { walls, .. } => walls.spark(x, y),
+			Scope::Chunk { chunk, walls, .. } => {
+				chunk.sparked_walls.push((x, y));
+				walls.can_spark(x, y)
+			}
+		}
+	}
+
+	/// Blasts are applied by the simulation once the particle update is done
+	pub(super) fn add_blast(&mut self, blast: Blast) {
+		match &mut self.scope {
+			Scope::Board { blasts, .. } => blasts.push(blast),
+			Scope::Chunk { chunk, .. } => chunk.blasts.push(blast)
+		}
+	}
+}
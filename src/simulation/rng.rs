@@ -0,0 +1,62 @@
+use rand::{Error, RngCore};
+
+/// Seedable xorshift64* generator used by everything in the simulation
+/// The whole state is a single u64, so it's cheap to save and restore
+#[derive(Clone)]
+pub struct SimRng {
+	seed:  u64,
+	state: u64
+}
+
+impl SimRng {
+	pub fn new(seed: u64) -> Self {
+		// Xorshift gets stuck at 0, so the seed is scrambled with splitmix64 first
+		let mut z = seed.wrapping_add(0x9E3779B97F4A7C15);
+		z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+		z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+
+		Self {
+			seed,
+			state: (z ^ (z >> 31)).max(1)
+		}
+	}
+
+	pub fn seed(&self) -> u64 {
+		self.seed
+	}
+
+	pub fn state(&self) -> u64 {
+		self.state
+	}
+
+	/// Restores a state previously returned by `state()`
+	pub fn restore(&mut self, seed: u64, state: u64) {
+		self.seed = seed;
+		self.state = state.max(1);
+	}
+}
+
+impl RngCore for SimRng {
+	fn next_u32(&mut self) -> u32 {
+		(self.next_u64() >> 32) as u32
+	}
+
+	fn next_u64(&mut self) -> u64 {
+		self.state ^= self.state >> 12;
+		self.state ^= self.state << 25;
+		self.state ^= self.state >> 27;
+		self.state.wrapping_mul(0x2545F4914F6CDD1D)
+	}
+
+	fn fill_bytes(&mut self, dest: &mut [u8]) {
+		for chunk in dest.chunks_mut(8) {
+			let bytes = self.next_u64().to_le_bytes();
+			chunk.copy_from_slice(&bytes[..chunk.len()]);
+		}
+	}
+
+	fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+		self.fill_bytes(dest);
+		Ok(())
+	}
+}
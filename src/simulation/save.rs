@@ -0,0 +1,487 @@
+use std::fmt;
+
+use miniz_oxide::deflate::compress_to_vec_zlib;
+use miniz_oxide::inflate::decompress_to_vec_zlib_with_limit;
+
+use crate::simulation::air::CELL;
+use crate::simulation::gravity::GravityMode;
+use crate::simulation::heat::{MAX_TEMP, MIN_TEMP};
+use crate::simulation::properties::PropKind;
+use crate::simulation::sim::{EdgeMode, Simulation, MAX_VELOCITY};
+use crate::simulation::walls::WallType;
+use crate::simulation::Particle;
+
+pub const MAGIC: [u8; 4] = *b"PWRS";
+pub const VERSION: u16 = 2; // Bump when the layout changes, older versions have to keep loading
+
+const MAX_SIDE: u32 = 2048; // Biggest board WebGL can show, bigger ones are rejected so a small file can't eat all the memory
+const MAX_DATA: usize = 1 << 28; // Bytes after decompression
+const COMPRESSION: u8 = 6;
+
+// Layout, little endian, everything after the version is zlib compressed:
+// magic, version u16
+// width u32, height u32, edge mode u8, gravity mode u8, gravity strength f32
+// since version 2: RNG seed u64, RNG state u64, tick u64
+// elements u16, per element: id u16, name, properties u8, per property: name
+// walls on the air grid, one `WallType::ALL` index u8 per cell
+// particles u32, per particle: type u16, x, y, vx, vy, temp f32, a u16 per property of its element
+// Names are a u8 length followed by UTF-8
+// Elements and properties are saved by name, so ids can change between versions
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadError {
+	NotASave,
+	NewerVersion(u16),
+	Corrupted
+}
+
+impl fmt::Display for LoadError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			LoadError::NotASave => write!(f, "not a save file"),
+			LoadError::NewerVersion(v) => write!(f, "made by a newer version (save version {})", v),
+			LoadError::Corrupted => write!(f, "save is corrupted")
+		}
+	}
+}
+
+/// Element as it was in the save, properties are the names of what's in its particles' slots
+struct SavedElement {
+	name:       String,
+	properties: Vec<String>
+}
+
+/// Everything read from a save, checked before any of it touches the simulation
+struct SaveData {
+	width:     usize,
+	height:    usize,
+	edge_mode: EdgeMode,
+	gravity:   (GravityMode, f32),
+	rng:       Option<(u64, u64, u64)>, // Seed, state and tick, older versions don't have them
+	elements:  Vec<(u16, SavedElement)>,
+	walls:     Vec<WallType>,
+	parts:     Vec<(u16, Particle, Vec<u16>)> // Saved type, particle and values of its properties
+}
+
+const EDGE_MODES: [EdgeMode; 3] = [EdgeMode::Void, EdgeMode::Solid, EdgeMode::Wrap];
+const GRAVITY_MODES: [GravityMode; 4] = [
+	GravityMode::Off,
+	GravityMode::Vertical,
+	GravityMode::Radial,
+	GravityMode::Newtonian
+];
+
+impl Simulation {
+	/// Board, walls and settings, see `load`
+	pub fn save(&self) -> Vec<u8> {
+		let mut body = Vec::new();
+		body.extend((self.get_width() as u32).to_le_bytes());
+		body.extend((self.get_height() as u32).to_le_bytes());
+		body.push(index_of(&EDGE_MODES, self.edge_mode));
+		body.push(index_of(&GRAVITY_MODES, self.gravity.mode));
+		body.extend(self.gravity.strength.to_le_bytes());
+		// Sparks compare their tmp to the tick, so it's saved along with the RNG
+		let rng = &self.rng;
+		for value in [rng.seed(), rng.state(), self.get_tick()] {
+			body.extend(value.to_le_bytes());
+		}
+
+		let elements = &self.element_manager.elements;
+		body.extend((elements.len() as u16).to_le_bytes());
+		for el in elements {
+			body.extend(el.id.to_le_bytes());
+			write_name(&mut body, el.name);
+			body.push(el.properties.len() as u8);
+			for prop in el.properties {
+				write_name(&mut body, prop.name);
+			}
+		}
+
+		for (cx, cy) in wall_grid_cells(self.get_width(), self.get_height()) {
+			let wall = self.get_walls().get(cx * CELL, cy * CELL);
+			body.push(index_of(&WallType::ALL, wall));
+		}
+
+		body.extend((self.get_part_count() as u32).to_le_bytes());
+		for (_, pt) in self.iter_parts() {
+			body.extend(pt.p_type.to_le_bytes());
+			for value in [pt.x, pt.y, pt.vx, pt.vy, pt.temp] {
+				body.extend(value.to_le_bytes());
+			}
+			for prop in pt.get_type(&self.element_manager).properties {
+				body.extend(prop.get(pt).to_le_bytes());
+			}
+		}
+
+		let mut data = MAGIC.to_vec();
+		data.extend(VERSION.to_le_bytes());
+		data.extend(compress_to_vec_zlib(&body, COMPRESSION));
+		data
+	}
+
+	/// New simulation of the size the save was made with
+	pub fn from_save(data: &[u8]) -> Result<Simulation, LoadError> {
+		let save = SaveData::read(data)?;
+		let mut sim = Simulation::new(save.width, save.height);
+		sim.apply_save(save);
+		Ok(sim)
+	}
+
+	/// Replaces the board with the save, the board isn't touched if it returns an error
+	/// Parts of a bigger board are cut off, particles of elements this version doesn't have are left out
+	pub fn load(&mut self, data: &[u8]) -> Result<(), LoadError> {
+		let save = SaveData::read(data)?;
+		self.apply_save(save);
+		Ok(())
+	}
+
+	fn apply_save(&mut self, save: SaveData) {
+		self.replace_parts(&[]);
+		self.air.pressure.fill(0.0);
+		self.air.vx.fill(0.0);
+		self.air.vy.fill(0.0);
+		self.edge_mode = save.edge_mode;
+		(self.gravity.mode, self.gravity.strength) = save.gravity;
+		if let Some((seed, state, tick)) = save.rng {
+			self.rng.restore(seed, state);
+			self.tick = tick;
+		}
+
+		let (cells_x, _) = wall_grid(save.width, save.height);
+		let (width, height) = (self.get_width(), self.get_height());
+		for (cx, cy) in wall_grid_cells(width, height) {
+			let wall = if cx < cells_x {
+				save.walls
+					.get(cx + cy * cells_x)
+					.copied()
+					.unwrap_or(WallType::None)
+			}
+			else {
+				WallType::None
+			};
+			self.set_wall(cx * CELL, cy * CELL, wall);
+		}
+
+		// Saved id to the current one, by name
+		let ids: Vec<(u16, Option<u16>)> = save
+			.elements
+			.iter()
+			.map(|(id, el)| {
+				(
+					*id,
+					self.element_manager.get_element(&el.name).map(|el| el.id)
+				)
+			})
+			.collect();
+		let current_id = |saved: u16| {
+			ids.iter()
+				.find(|(id, _)| *id == saved)
+				.and_then(|(_, current)| *current)
+		};
+
+		for (saved_type, mut pt, values) in save.parts {
+			let Some(p_type) = current_id(saved_type)
+			else {
+				continue;
+			};
+			let Some((_, saved)) = save.elements.iter().find(|(id, _)| *id == saved_type)
+			else {
+				continue;
+			};
+
+			let el = &self.element_manager.elements[p_type as usize];
+			pt.p_type = p_type;
+			// Values no particle could reach would break movement and heat
+			pt.vx = pt.vx.clamp(-MAX_VELOCITY, MAX_VELOCITY);
+			pt.vy = pt.vy.clamp(-MAX_VELOCITY, MAX_VELOCITY);
+			pt.temp = pt.temp.clamp(MIN_TEMP, MAX_TEMP);
+			pt.prop1 = el.default.prop1;
+			pt.prop2 = el.default.prop2;
+			pt.prop3 = el.default.prop3;
+			for (name, value) in saved.properties.iter().zip(values) {
+				let Some(prop) = el.properties.iter().find(|prop| prop.name == name)
+				else {
+					continue;
+				};
+				let value = if prop.kind == PropKind::Element {
+					current_id(value).unwrap_or(0)
+				}
+				else {
+					value
+				};
+				prop.set(&mut pt, value);
+			}
+			self.add_part(pt);
+		}
+	}
+}
+
+impl SaveData {
+	fn read(data: &[u8]) -> Result<Self, LoadError> {
+		if data.len() < 6 || data[..4] != MAGIC {
+			return Err(LoadError::NotASave);
+		}
+		let version = u16::from_le_bytes([data[4], data[5]]);
+		if version > VERSION {
+			return Err(LoadError::NewerVersion(version));
+		}
+		let body = decompress_to_vec_zlib_with_limit(&data[6..], MAX_DATA)
+			.map_err(|_| LoadError::Corrupted)?;
+
+		let mut r = Reader {
+			data: &body,
+			pos:  0
+		};
+		let (width, height) = (r.u32()?, r.u32()?);
+		if width == 0 || height == 0 || width > MAX_SIDE || height > MAX_SIDE {
+			return Err(LoadError::Corrupted);
+		}
+		let (width, height) = (width as usize, height as usize);
+		let edge_mode = *EDGE_MODES
+			.get(r.u8()? as usize)
+			.ok_or(LoadError::Corrupted)?;
+		let gravity_mode = *GRAVITY_MODES
+			.get(r.u8()? as usize)
+			.ok_or(LoadError::Corrupted)?;
+		let strength = r.f32()?;
+		let rng = if version >= 2 {
+			Some((r.u64()?, r.u64()?, r.u64()?))
+		}
+		else {
+			None
+		};
+
+		let mut elements = Vec::new();
+		for _ in 0..r.u16()? {
+			let id = r.u16()?;
+			let name = r.name()?;
+			let mut properties = Vec::new();
+			for _ in 0..r.u8()? {
+				properties.push(r.name()?);
+			}
+			elements.push((id, SavedElement { name, properties }));
+		}
+
+		let mut walls = Vec::new();
+		for _ in wall_grid_cells(width, height) {
+			walls.push(
+				*WallType::ALL
+					.get(r.u8()? as usize)
+					.ok_or(LoadError::Corrupted)?
+			);
+		}
+
+		let mut parts = Vec::new();
+		for _ in 0..r.u32()? {
+			let p_type = r.u16()?;
+			let (_, el) = elements
+				.iter()
+				.find(|(id, _)| *id == p_type && p_type != 0)
+				.ok_or(LoadError::Corrupted)?;
+
+			let mut pt = Particle::default();
+			(pt.x, pt.y, pt.vx, pt.vy, pt.temp) =
+				(r.f32()?, r.f32()?, r.f32()?, r.f32()?, r.f32()?);
+			let mut values = Vec::new();
+			for _ in &el.properties {
+				values.push(r.u16()?);
+			}
+			parts.push((p_type, pt, values));
+		}
+
+		if r.pos != body.len() {
+			return Err(LoadError::Corrupted);
+		}
+
+		Ok(Self {
+			width,
+			height,
+			edge_mode,
+			gravity: (gravity_mode, strength),
+			rng,
+			elements,
+			walls,
+			parts
+		})
+	}
+}
+
+/// Reads values in the order they were written, running out of data means the save is corrupted
+struct Reader<'a> {
+	data: &'a [u8],
+	pos:  usize
+}
+
+impl Reader<'_> {
+	fn bytes<const N: usize>(&mut self) -> Result<[u8; N], LoadError> {
+		let bytes = self
+			.data
+			.get(self.pos..self.pos + N)
+			.ok_or(LoadError::Corrupted)?;
+		self.pos += N;
+		Ok(bytes.try_into().unwrap())
+	}
+
+	fn u8(&mut self) -> Result<u8, LoadError> {
+		Ok(self.bytes::<1>()?[0])
+	}
+
+	fn u16(&mut self) -> Result<u16, LoadError> {
+		Ok(u16::from_le_bytes(self.bytes()?))
+	}
+
+	fn u32(&mut self) -> Result<u32, LoadError> {
+		Ok(u32::from_le_bytes(self.bytes()?))
+	}
+
+	fn u64(&mut self) -> Result<u64, LoadError> {
+		Ok(u64::from_le_bytes(self.bytes()?))
+	}
+
+	/// Only finite values are valid
+	fn f32(&mut self) -> Result<f32, LoadError> {
+		Some(f32::from_le_bytes(self.bytes()?))
+			.filter(|v| v.is_finite())
+			.ok_or(LoadError::Corrupted)
+	}
+
+	fn name(&mut self) -> Result<String, LoadError> {
+		let len = self.u8()? as usize;
+		let bytes = self
+			.data
+			.get(self.pos..self.pos + len)
+			.ok_or(LoadError::Corrupted)?;
+		self.pos += len;
+		String::from_utf8(bytes.to_vec()).map_err(|_| LoadError::Corrupted)
+	}
+}
+
+fn write_name(data: &mut Vec<u8>, name: &str) {
+	let name = &name.as_bytes()[..name.len().min(u8::MAX as usize)];
+	data.push(name.len() as u8);
+	data.extend(name);
+}
+
+fn index_of<T: PartialEq>(values: &[T], value: T) -> u8 {
+	values.iter().position(|v| *v == value).unwrap_or(0) as u8
+}
+
+/// Size of the wall grid in cells
+fn wall_grid(width: usize, height: usize) -> (usize, usize) {
+	(width.div_ceil(CELL), height.div_ceil(CELL))
+}
+
+fn wall_grid_cells(width: usize, height: usize) -> impl Iterator<Item=(usize, usize)> {
+	let (cells_x, cells_y) = wall_grid(width, height);
+	(0..cells_y).flat_map(move |cy| (0..cells_x).map(move |cx| (cx, cy)))
+}
+
+#[cfg(test)]
+mod tests {
+	use miniz_oxide::inflate::decompress_to_vec_zlib;
+
+	use super::*;
+	use crate::simulation::elements::{EL_DUST, EL_FIRE, EL_WATR, EL_WOOD};
+	use crate::simulation::properties::{CTYPE, LIFE};
+
+	fn board(sim: &Simulation) -> Vec<Particle> {
+		sim.iter_parts().map(|(_, pt)| pt.clone()).collect()
+	}
+
+	fn same_board(a: &Simulation, b: &Simulation) -> bool {
+		let (a, b) = (board(a), board(b));
+		a.len() == b.len() &&
+			a.iter().zip(&b).all(|(a, b)| {
+				(a.p_type, a.prop1, a.prop2, a.prop3) == (b.p_type, b.prop1, b.prop2, b.prop3) &&
+					[a.x, a.y, a.vx, a.vy, a.temp] == [b.x, b.y, b.vx, b.vy, b.temp]
+			})
+	}
+
+	fn test_board() -> Simulation {
+		let mut sim = Simulation::new(64, 48);
+		sim.set_seed(4);
+		sim.edge_mode = EdgeMode::Wrap;
+		sim.gravity.mode = GravityMode::Radial;
+		for x in 8..56 {
+			sim.create_part([EL_DUST.id, EL_WATR.id, EL_WOOD.id][x % 3], x, 20);
+		}
+		sim.set_wall(4, 4, WallType::Conductor);
+		let fire = sim.create_part(EL_FIRE.id, 30, 40).unwrap();
+		CTYPE.set(&mut sim.parts[fire], EL_WOOD.id);
+		LIFE.set(&mut sim.parts[fire], 77);
+		sim.step_n(5);
+		sim
+	}
+
+	#[test]
+	fn round_trip() {
+		let mut sim = test_board();
+		let data = sim.save();
+		let mut loaded = Simulation::from_save(&data).unwrap();
+
+		assert!(same_board(&sim, &loaded));
+		assert_eq!(loaded.edge_mode, EdgeMode::Wrap);
+		assert_eq!(loaded.gravity.mode, GravityMode::Radial);
+		assert_eq!(loaded.get_walls().get(5, 5), WallType::Conductor);
+		assert_eq!(loaded.get_tick(), sim.get_tick());
+		assert_eq!(loaded.rng().state(), sim.rng().state());
+
+		// Both continue the same way
+		sim.step_n(10);
+		loaded.step_n(10);
+		assert!(same_board(&sim, &loaded));
+	}
+
+	#[test]
+	fn loads_version_1() {
+		let sim = test_board();
+		let data = sim.save();
+		let mut body = decompress_to_vec_zlib(&data[6..]).unwrap();
+		body.drain(14..38); // Seed, RNG state and tick
+		let mut v1 = MAGIC.to_vec();
+		v1.extend(1u16.to_le_bytes());
+		v1.extend(compress_to_vec_zlib(&body, COMPRESSION));
+
+		let mut loaded = Simulation::new(64, 48);
+		loaded.step_n(3);
+		loaded.load(&v1).unwrap();
+		assert!(same_board(&sim, &loaded));
+		assert_eq!(loaded.get_tick(), 3);
+	}
+
+	#[test]
+	fn rejects_bad_saves() {
+		let data = test_board().save();
+		assert_eq!(
+			Simulation::from_save(b"PWR").err(),
+			Some(LoadError::NotASave)
+		);
+		assert_eq!(
+			Simulation::from_save(b"OPS1\x02\x00").err(),
+			Some(LoadError::NotASave)
+		);
+
+		let mut newer = data.clone();
+		newer[4..6].copy_from_slice(&(VERSION + 1).to_le_bytes());
+		assert_eq!(
+			Simulation::from_save(&newer).err(),
+			Some(LoadError::NewerVersion(VERSION + 1))
+		);
+
+		let mut sim = test_board();
+		let before = board(&sim);
+		for len in 6..data.len() {
+			assert!(sim.load(&data[..len]).is_err());
+		}
+		assert_eq!(board(&sim).len(), before.len());
+
+		let mut body = decompress_to_vec_zlib(&data[6..]).unwrap();
+		body[..4].copy_from_slice(&(MAX_SIDE + 1).to_le_bytes());
+		let mut huge = data[..6].to_vec();
+		huge.extend(compress_to_vec_zlib(&body, COMPRESSION));
+		assert_eq!(
+			Simulation::from_save(&huge).err(),
+			Some(LoadError::Corrupted)
+		);
+	}
+}
@@ -1,68 +1,207 @@
+use std::sync::atomic::AtomicU64;
+use std::sync::Mutex;
+use std::thread;
+
 use rand::prelude::*;
 use rust_bresenham::Bresenham;
 
+use crate::simulation::air::{Air, CELL};
+use crate::simulation::chunks::{Chunk, CHUNK_HEIGHT};
 use crate::simulation::elements::*;
-use crate::simulation::Particle;
-
-// TODO: find a good window / sim size
-pub const WINW: usize = (1.0 * 720.0) as usize;
-pub const WINH: usize = (1.0 * 480.0) as usize;
-pub const XRES: usize = WINW;
-pub const YRES: usize = WINH;
-pub const XYRES: usize = XRES * YRES;
+use crate::simulation::explosives::Blast;
+use crate::simulation::gravity::Gravity;
+use crate::simulation::region::Region;
+use crate::simulation::rng::SimRng;
+use crate::simulation::walls::{WallType, Walls};
+use crate::simulation::{Particle, UpdateContext};
+
+pub const MAX_VELOCITY: f32 = 10.0; // Below `REACH`, so particles can move freely within their chunk's region
+
+// Neighbouring pixels in clockwise order, starting to the right
+pub(super) const DIRECTIONS: [(isize, isize); 8] = [
+	(1, 0),
+	(1, 1),
+	(0, 1),
+	(-1, 1),
+	(-1, 0),
+	(-1, -1),
+	(0, -1),
+	(1, -1)
+];
+
+/// What happens to particles that reach the edge of the board
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EdgeMode {
+	Void,  // Particles leaving the board are killed
+	Solid, // Particles collide with the edge
+	Wrap   // Particles come out on the opposite side, neighbours wrap around too
+}
 
-type ParticleHandle = usize;
+pub type ParticleHandle = usize;
 pub struct Simulation {
 	pub parts:           Box<[Particle]>,
 	pub pmap:            Box<[Option<usize>]>,
+	pub photons:         Box<[Option<usize>]>, // Same as pmap, but for energy particles
+	pub air:             Air,
+	pub gravity:         Gravity,
 	pub paused:          bool,
 	pub element_manager: ElementManager,
-	part_count:          usize
+	pub edge_mode:       EdgeMode,
+
+	threads: usize,
+	width:   usize,
+	height:  usize,
+
+	pub(super) rng:        SimRng,
+	pub(super) part_count: usize,
+	pub(super) free_list:  Vec<ParticleHandle>, // Free slots in parts, lowest index is at the end
+	pub(super) parts_end:  usize,               // One past the highest live particle index
+	pub(super) tick:       u64,
+	pub(super) walls:      Walls,
+	pub(super) chunks:     Vec<Chunk>,
+	pub(super) blasts:     Vec<Blast>, // Explosions set off outside of the particle update
+	pub(super) owners:     Box<[AtomicU64]>, // Chunk owning each particle, see `chunk_regions`
+	pub(super) owner_tag:  u64         // First tag of the next pass
 }
 impl Simulation {
-	pub fn new() -> Self {
-		let p = vec![Particle::default(); XYRES * 2].into_boxed_slice();
-		let pm = vec![None; XYRES].into_boxed_slice();
+	/// Size is in sim pixels, the board can't be resized later
+	pub fn new(width: usize, height: usize) -> Self {
+		assert!(width > 0 && height > 0, "Simulation size can't be 0");
+
+		let p = vec![Particle::default(); width * height * 2].into_boxed_slice();
+		let pm = vec![None; width * height].into_boxed_slice();
+		let free = (0..p.len()).rev().collect();
+		// Leftover rows go to the last chunk, so chunks are never shorter than CHUNK_HEIGHT
+		let chunk_count = (height / CHUNK_HEIGHT).max(1);
+
+		// Browsers can't spawn threads
+		let workers = if cfg!(target_arch = "wasm32") {
+			1
+		}
+		else {
+			thread::available_parallelism().map_or(1, |n| n.get())
+		};
+
+		let owners = (0..p.len()).map(|_| AtomicU64::new(0)).collect();
 
 		Self {
-			parts:           p,
-			pmap:            pm,
-			paused:          false,
+			parts: p,
+			pmap: pm.clone(),
+			photons: pm,
+			air: Air::new(width, height),
+			gravity: Gravity::new(width, height),
+			paused: false,
 			element_manager: ElementManager::new(),
-			part_count:      0
+			edge_mode: EdgeMode::Void,
+			rng: SimRng::new(random()),
+			part_count: 0,
+			free_list: free,
+			parts_end: 0,
+			threads: workers,
+			chunks: (0..chunk_count)
+				.map(|i| Chunk::new(i, chunk_count))
+				.collect(),
+			width,
+			height,
+			tick: 0,
+			walls: Walls::new(width, height),
+			blasts: Vec::new(),
+			owners,
+			owner_tag: 1
 		}
 	}
 
-	/// Adds particle
-	pub fn add_part(&mut self, part: Particle) -> Option<ParticleHandle> {
-		if part.p_type == 0 {
-			return None;
-		}
-		if self.get_id(part.x as usize, part.y as usize).is_some() {
-			return None;
+	/// Reseeds the RNG, two simulations with the same seed and state will step identically
+	pub fn set_seed(&mut self, seed: u64) {
+		self.rng = SimRng::new(seed);
+	}
+
+	/// Used outside of the particle update, every chunk gets its own RNG for it
+	pub fn rng(&mut self) -> &mut SimRng {
+		&mut self.rng
+	}
+
+	/// Number of threads used by `step`, the result is the same for any number of threads
+	pub fn set_threads(&mut self, threads: usize) {
+		self.threads = threads.max(1);
+	}
+
+	pub fn get_threads(&self) -> usize {
+		self.threads
+	}
+
+	pub fn get_width(&self) -> usize {
+		self.width
+	}
+
+	pub fn get_height(&self) -> usize {
+		self.height
+	}
+
+	/// Number of steps done so far
+	pub fn get_tick(&self) -> u64 {
+		self.tick
+	}
+
+	pub fn get_walls(&self) -> &Walls {
+		&self.walls
+	}
+
+	/// Sets the wall of the cell containing x, y and kills particles the new wall doesn't let in
+	pub fn set_wall(&mut self, x: usize, y: usize, wall: WallType) {
+		if !self.walls.set(x, y, wall) {
+			return;
 		}
 
-		// find first empty spot
-		for i in 0..self.parts.len() {
-			if self.parts[i].p_type == 0 {
-				self.part_count += 1;
-				self.pmap[part.x as usize + (part.y as usize * XRES)] = Some(i);
-				self.parts[i] = part;
-				return Some(i);
+		let (cx, cy) = (x / CELL * CELL, y / CELL * CELL);
+		let (width, height) = (self.width, self.height);
+		let mut region = self.region();
+		for py in cy..(cy + CELL).min(height) {
+			for px in cx..(cx + CELL).min(width) {
+				if let Some(id) = region.get_id(px, py) {
+					if region.wall_blocks(id, px, py) {
+						region.kill_part(id).expect("Tried to kill invalid part");
+					}
+				}
 			}
 		}
-		None
 	}
 
-	pub fn kill_part(&mut self, id: ParticleHandle) -> Result<(), ()> {
-		if id >= self.parts.len() || self.parts[id].p_type == 0 {
-			return Err(());
-		}
+	/// Adds particle
+	pub fn add_part(&mut self, part: Particle) -> Option<ParticleHandle> {
+		self.region().add_part(part)
+	}
+
+	/// Adds particle with default properties of its element
+	pub fn create_part(&mut self, p_type: u16, x: usize, y: usize) -> Option<ParticleHandle> {
+		self.region().create_part(p_type, x, y)
+	}
+
+	/// Returns false if the particle isn't alive
+	pub fn kill_part(&mut self, id: ParticleHandle) -> bool {
+		self.region().kill_part(id).is_ok()
+	}
+
+	/// Replaces all particles, handles stay the same as their index in parts
+	pub(super) fn replace_parts(&mut self, parts: &[Particle]) {
+		let end = self.parts_end.max(parts.len());
+		self.parts[..end].fill(Particle::default());
+		self.parts[..parts.len()].clone_from_slice(parts);
+
+		self.free_list = (0..self.parts.len())
+			.rev()
+			.filter(|&i| self.parts[i].p_type == 0)
+			.collect();
+		self.part_count = self.parts.len() - self.free_list.len();
+		self.parts_end = parts.len();
+		self.shrink_parts_end();
+		self.update_p_map();
+	}
 
-		self.pmap[self.parts[id].x as usize + (self.parts[id].y as usize * XRES)] = None;
-		self.parts[id] = Particle::default();
-		self.part_count -= 1;
-		Ok(())
+	fn shrink_parts_end(&mut self) {
+		while self.parts_end > 0 && self.parts[self.parts_end - 1].p_type == 0 {
+			self.parts_end -= 1;
+		}
 	}
 
 	pub fn get_part(&self, id: ParticleHandle) -> &Particle {
@@ -77,67 +216,233 @@ impl Simulation {
 		self.part_count
 	}
 
+	/// All live particles have a lower index than this
+	pub fn get_parts_end(&self) -> usize {
+		self.parts_end
+	}
+
+	/// Live particles with their handles, in order of their index
+	pub fn iter_parts(&self) -> impl Iterator<Item=(ParticleHandle, &Particle)> {
+		self.parts[..self.parts_end]
+			.iter()
+			.enumerate()
+			.filter(|(_, pt)| pt.p_type != 0)
+	}
+
 	pub fn get_pmap(&self, x: usize, y: usize) -> Option<&Particle> {
-		if x >= XRES || y >= YRES {
+		if x >= self.width || y >= self.height {
 			return None;
 		}
 
-		let val = self.pmap[x + (y * XRES)];
+		let val = self.pmap[x + (y * self.width)];
 		val?;
 		Some(&self.parts[val.unwrap()])
 	}
 
 	pub fn get_id(&self, x: usize, y: usize) -> Option<ParticleHandle> {
-		if x >= XRES || y >= YRES {
+		if x >= self.width || y >= self.height {
 			return None;
 		}
 
-		self.pmap[x + (y * XRES)]
+		self.pmap[x + (y * self.width)]
 	}
 
-	pub fn update_p_map(&mut self) {
-		let mut counter = 0;
+	/// Maps a position onto the board, positions outside of it wrap around in `EdgeMode::Wrap`
+	/// Returns None if the position is outside the board
+	pub fn wrap_pos(&self, x: isize, y: isize) -> Option<(usize, usize)> {
+		let (w, h) = (self.width as isize, self.height as isize);
+		if self.edge_mode == EdgeMode::Wrap {
+			return Some((x.rem_euclid(w) as usize, y.rem_euclid(h) as usize));
+		}
+
+		if x < 0 || y < 0 || x >= w || y >= h {
+			return None;
+		}
+		Some((x as usize, y as usize))
+	}
 
+	/// Same as `get_id`, but takes positions outside the board which wrap around in `EdgeMode::Wrap`
+	pub fn get_id_wrapped(&self, x: isize, y: isize) -> Option<ParticleHandle> {
+		let (x, y) = self.wrap_pos(x, y)?;
+		self.get_id(x, y)
+	}
+
+	pub fn update_p_map(&mut self) {
 		self.pmap.fill(None);
-		for i in 0..self.parts.len() {
-			if counter >= self.part_count {
-				break;
+		self.photons.fill(None);
+		for i in 0..self.parts_end {
+			if self.parts[i].p_type != 0 {
+				let index = self.parts[i].x as usize + (self.parts[i].y as usize * self.width);
+				if self.is_energy(i) {
+					self.photons[index] = Some(i);
+				}
+				else {
+					self.pmap[index] = Some(i);
+				}
 			}
+		}
+	}
 
-			if self.parts[i].p_type != 0 {
-				let index = self.parts[i].x as usize + (self.parts[i].y as usize * XRES);
-				self.pmap[index] = Some(i);
-				counter += 1;
+	/// Swaps positions of two particles, energy particles can't be swapped
+	/// Returns false if either particle isn't alive or is energy
+	pub fn swap_parts(&mut self, a: ParticleHandle, b: ParticleHandle) -> bool {
+		self.region().swap_parts(a, b)
+	}
+
+	/// Changing type to 0 kills the particle, so does turning energy into matter on a taken spot
+	/// Returns true if the particle was killed
+	pub(super) fn change_type(&mut self, pt_id: ParticleHandle, p_type: u16) -> bool {
+		self.region().change_type(pt_id, p_type)
+	}
+
+	/// Sorts particles into chunks and gives every chunk its own RNG
+	fn prepare_chunks(&mut self) {
+		let seed = self.rng.next_u64();
+		for (i, chunk) in self.chunks.iter_mut().enumerate() {
+			chunk.reset(SimRng::new(seed.wrapping_add(i as u64)));
+		}
+
+		for pt_id in 0..self.parts_end {
+			let pt = &self.parts[pt_id];
+			if pt.p_type != 0 {
+				let chunk = (pt.y as usize / CHUNK_HEIGHT).min(self.chunks.len() - 1);
+				self.chunks[chunk].parts.push(pt_id);
 			}
 		}
 	}
 
-	fn kill_out_of_bounds(&mut self, pt_id: ParticleHandle) -> bool {
-		let pt = &self.parts[pt_id];
-		let x = pt.x as isize;
-		let y = pt.y as isize;
+	/// Runs `update` on all even chunks and then on all odd ones
+	/// Chunks in the same pass get disjoint regions, so they can run on separate threads
+	fn run_chunks(&mut self, update: fn(&mut Region)) {
+		let threads = self.threads;
+		let mut rows = Vec::new();
 
-		if x < 0 || x >= XRES as isize || y < 0 || y >= YRES as isize {
-			self.parts[pt_id] = Particle::default();
-			self.part_count -= 1;
-			return true;
+		for pass in 0..3 {
+			let mut regions = self.chunk_regions(pass, &mut rows);
+			let threads = threads.min(regions.len());
+
+			if threads <= 1 {
+				for region in regions.iter_mut() {
+					update(region);
+				}
+				continue;
+			}
+
+			let queue = Mutex::new(regions);
+			thread::scope(|s| {
+				for _ in 0..threads {
+					s.spawn(|| loop {
+						let Some(mut region) = queue.lock().unwrap().pop()
+						else {
+							break;
+						};
+						update(&mut region);
+					});
+				}
+			});
+		}
+	}
+
+	pub(super) fn chunk_pass(&self, chunk: usize) -> usize {
+		let count = self.chunks.len();
+
+		// With wrapping the first and last chunk touch, so an odd last chunk gets its own pass
+		if self.edge_mode == EdgeMode::Wrap && count > 1 && count % 2 == 1 && chunk == count - 1 {
+			return 2;
+		}
+		chunk % 2
+	}
+
+	/// Gives ids taken and freed by chunks back to the simulation
+	fn finish_chunks(&mut self) {
+		Chunk::remove_taken(&self.chunks, &mut self.free_list);
+		for chunk in self.chunks.iter_mut() {
+			for &(x, y) in &chunk.sparked_walls {
+				self.walls.spark(x, y);
+			}
+			self.part_count = self.part_count + chunk.created - chunk.freed.len();
+			self.parts_end = self.parts_end.max(chunk.parts_end);
+			self.free_list.append(&mut chunk.freed);
+		}
+		self.shrink_parts_end();
+	}
+
+	pub fn step(&mut self) {
+		self.air.update(&self.walls);
+		self.gravity
+			.update(&self.parts[..self.parts_end], &self.element_manager);
+
+		self.update_wall_sparks();
+
+		self.prepare_chunks();
+		self.run_chunks(|region| region.conduct_heat());
+		self.run_chunks(|region| region.update_chunk());
+		self.finish_chunks();
+		self.apply_blasts();
+
+		self.update_p_map();
+		self.update_life();
+		self.tick += 1;
+	}
+
+	/// Runs `step` n times, `paused` only stops the game loop
+	pub fn step_n(&mut self, n: usize) {
+		for _ in 0..n {
+			self.step();
+		}
+	}
+}
+
+impl Region<'_> {
+	/// Brings a particle that left the board back according to the edge mode
+	/// Returns true if the particle was killed
+	fn apply_edge_mode(&mut self, pt_id: ParticleHandle) -> bool {
+		let (w, h) = (self.width as f32, self.height as f32);
+		let pt = &mut self.parts[pt_id];
+
+		if (0.0..w).contains(&pt.x) && (0.0..h).contains(&pt.y) {
+			return false;
+		}
+
+		match self.edge_mode {
+			EdgeMode::Void => {
+				self.free_part(pt_id);
+				return true;
+			}
+			// Movement stops at the edge, so this shouldn't happen
+			EdgeMode::Solid => {
+				pt.x = pt.x.clamp(0.0, w - 1.0);
+				pt.y = pt.y.clamp(0.0, h - 1.0);
+			}
+			EdgeMode::Wrap => {
+				// Tiny negative values can round up to the size
+				pt.x = Some(pt.x.rem_euclid(w)).filter(|x| *x < w).unwrap_or(0.0);
+				pt.y = Some(pt.y.rem_euclid(h)).filter(|y| *y < h).unwrap_or(0.0);
+			}
 		}
 		false
 	}
 
+	/// Particles stay where they were if x, y is out of reach
 	fn move_to(&mut self, pt_id: ParticleHandle, x: f32, y: f32) {
 		let pt = &mut self.parts[pt_id];
-		self.pmap[pt.x as usize + pt.y as usize * XRES] = None;
+		let old = (pt.x, pt.y);
+		self.pmap[pt.x as usize + pt.y as usize * self.width] = None;
 
 		pt.x = x;
 		pt.y = y;
 
-		if self.kill_out_of_bounds(pt_id) {
+		if self.apply_edge_mode(pt_id) {
 			return;
 		}
 
+		let pt = &self.parts[pt_id];
+		if !self.in_reach(pt.x as usize, pt.y as usize) {
+			(self.parts[pt_id].x, self.parts[pt_id].y) = old;
+		}
+
 		let pt = &mut self.parts[pt_id];
-		self.pmap[pt.x as usize + pt.y as usize * XRES] = Some(pt_id);
+		self.pmap[pt.x as usize + pt.y as usize * self.width] = Some(pt_id);
 	}
 
 	// Borrow checker doesn't like stuff that results in subframe (:
@@ -145,13 +450,28 @@ impl Simulation {
 	// Returns true if collided
 	fn velocity_move(&mut self, pt_id: ParticleHandle) -> bool {
 		let pt = &self.parts[pt_id];
+		let el = pt.get_type(self.element_manager);
+		let (advection, loss) = (el.advection, el.loss);
+		let max_velocity = el.max_velocity.min(MAX_VELOCITY);
+		let (air_vx, air_vy) = self.air.velocity_at(pt.x as usize, pt.y as usize);
+
+		let pt = &mut self.parts[pt_id];
+		pt.vx = pt.vx * loss + air_vx * advection;
+		pt.vy = pt.vy * loss + air_vy * advection;
+		let speed = (pt.vx * pt.vx + pt.vy * pt.vy).sqrt();
+		if speed > max_velocity {
+			pt.vx *= max_velocity / speed;
+			pt.vy *= max_velocity / speed;
+		}
+		let pt = &self.parts[pt_id];
 
 		let vx = pt.vx;
 		let vy = pt.vy;
 		let px = pt.x as isize;
 		let py = pt.y as isize;
-		let rx = (pt.x + vx) as isize;
-		let ry = (pt.y + vy) as isize;
+		// Floored so small steps past the left or top edge don't round back onto the board
+		let rx = (pt.x + vx).floor() as isize;
+		let ry = (pt.y + vy).floor() as isize;
 
 		if px != rx || py != ry {
 			let mut points: Vec<_> = Bresenham::new((px, py), (rx, ry)).collect();
@@ -159,12 +479,32 @@ impl Simulation {
 
 			let mut prev = (px, py);
 			for (x, y) in points {
-				if self.get_pmap(x as usize, y as usize).is_some() {
-					self.move_to(pt_id, prev.0 as f32, prev.1 as f32);
+				let Some((bx, by)) = self.wrap_pos(x, y)
+				else {
+					if self.edge_mode == EdgeMode::Solid {
+						return self.collide(pt_id, prev, (x, y));
+					}
+					// Flew into the void, move_to below kills it
+					break;
+				};
 
-					self.parts[pt_id].vx = 0.0;
-					self.parts[pt_id].vy = 0.0;
-					return true;
+				// Running into the region's border stops the particle like a wall would
+				if !self.in_reach(bx, by) || self.wall_blocks(pt_id, bx, by) {
+					return self.collide(pt_id, prev, (x, y));
+				}
+
+				match self.get_id(bx, by) {
+					Some(other) if other == pt_id => {}
+					Some(other) if self.can_displace(pt_id, other) => {
+						self.move_to(pt_id, prev.0 as f32, prev.1 as f32);
+						self.swap_parts(pt_id, other);
+
+						self.parts[pt_id].vx = 0.0;
+						self.parts[pt_id].vy = 0.0;
+						return false;
+					}
+					Some(_) => return self.collide(pt_id, prev, (x, y)),
+					None => {}
 				}
 
 				prev = (x, y);
@@ -175,41 +515,332 @@ impl Simulation {
 		false
 	}
 
-	fn powder_move(&mut self, pt_id: ParticleHandle) {
+	/// Stops at pos after running into the pixel at hit
+	/// Velocity into the surface bounces back and velocity along it is slowed by friction
+	// Always returns true, so velocity_move can return it as collided
+	fn collide(&mut self, pt_id: ParticleHandle, pos: (isize, isize), hit: (isize, isize)) -> bool {
+		self.move_to(pt_id, pos.0 as f32, pos.1 as f32);
+
+		// Moving diagonally it can hit a wall on either side, or a corner which counts as both
+		let (dx, dy) = (hit.0 - pos.0, hit.1 - pos.1);
+		let wall_x = dx != 0 && !self.can_move_to(pt_id, pos.0 + dx, pos.1);
+		let wall_y = dy != 0 && !self.can_move_to(pt_id, pos.0, pos.1 + dy);
+		let (normal_x, normal_y) = if wall_x || wall_y {
+			(wall_x, wall_y)
+		}
+		else {
+			(dx != 0, dy != 0)
+		};
+
+		let el = self.parts[pt_id].get_type(self.element_manager);
+		let (bounce, friction) = (el.bounce, el.friction);
+		let pt = &mut self.parts[pt_id];
+		pt.vx *= if normal_x { -bounce } else { 1.0 - friction };
+		pt.vy *= if normal_y { -bounce } else { 1.0 - friction };
+		true
+	}
+
+	/// Index into `DIRECTIONS` closest to gravity at the particle, None without gravity
+	fn fall_dir(&self, pt_id: ParticleHandle) -> Option<usize> {
+		let pt = &self.parts[pt_id];
+		let (gx, gy) = self.gravity.at(pt.x, pt.y);
+		if gx == 0.0 && gy == 0.0 {
+			return None;
+		}
+
+		let angle = gy.atan2(gx) / std::f32::consts::FRAC_PI_4;
+		Some((angle.round() as isize).rem_euclid(8) as usize)
+	}
+
+	/// Moves diagonally down, down being the direction of gravity
+	/// Returns true if moved
+	fn powder_move(&mut self, pt_id: ParticleHandle) -> bool {
+		let Some(down) = self.fall_dir(pt_id)
+		else {
+			return false;
+		};
+		let dir = if self.rng().gen() { 1 } else { 7 };
+		let pt = &self.parts[pt_id];
+
+		// Next direction on either side of down
+		let (dx, dy) = DIRECTIONS[(down + 8 - dir) % 8];
+		self.try_move(pt_id, pt.x as isize + dx, pt.y as isize + dy)
+	}
+
+	/// Moves fluid sideways by up to `spread` cells, stopping early above a gap it can fall into
+	/// Returns true if moved
+	fn fluid_move(&mut self, pt_id: ParticleHandle) -> bool {
+		let Some(down) = self.fall_dir(pt_id)
+		else {
+			return false;
+		};
+		let (dx, dy) = DIRECTIONS[down];
 		let pt = &self.parts[pt_id];
-		// todo
-		if random() {
-			let pos = (pt.x as usize + 1, (pt.y + 1.0) as usize);
-			if self.get_pmap(pos.0, pos.1).is_none() {
-				self.move_to(pt_id, pos.0 as f32, pos.1 as f32);
+		let spread = pt.get_type(self.element_manager).spread as isize;
+		let (x, y) = (pt.x as isize, pt.y as isize);
+		let first_dir = if self.rng().gen() { 2 } else { 6 };
+
+		for dir in [first_dir, 8 - first_dir] {
+			// Sideways is perpendicular to down
+			let (sx, sy) = DIRECTIONS[(down + 8 - dir) % 8];
+			let mut target = None;
+			for i in 1..=spread {
+				let (nx, ny) = (x + sx * i, y + sy * i);
+				if !self.can_move_to(pt_id, nx, ny) {
+					break;
+				}
+
+				target = Some((nx, ny));
+				if self.can_move_to(pt_id, nx + dx, ny + dy) {
+					break;
+				}
 			}
+
+			if let Some((nx, ny)) = target {
+				return self.try_move(pt_id, nx, ny);
+			}
+		}
+		false
+	}
+
+	/// Random movement with per element gravity, gas that hits something tries a random neighbouring spot
+	fn gas_move(&mut self, pt_id: ParticleHandle) {
+		let diffusion = self.parts[pt_id].get_type(self.element_manager).diffusion;
+
+		let (rx, ry): (f32, f32) = (
+			self.rng().gen_range(-1.0..1.0),
+			self.rng().gen_range(-1.0..1.0)
+		);
+
+		let pt = &mut self.parts[pt_id];
+		let gravity = pt.get_type(self.element_manager).gravity;
+		let (gx, gy) = self.gravity.at(pt.x, pt.y);
+		pt.vx += diffusion * rx + gx * gravity;
+		pt.vy += diffusion * ry + gy * gravity;
+
+		if self.velocity_move(pt_id) && self.parts[pt_id].p_type != 0 {
+			let (dx, dy): (isize, isize) =
+				(self.rng().gen_range(-1..=1), self.rng().gen_range(-1..=1));
+			let pt = &self.parts[pt_id];
+			self.try_move(pt_id, pt.x as isize + dx, pt.y as isize + dy);
 		}
+	}
+
+	fn can_move_to(&self, pt_id: ParticleHandle, x: isize, y: isize) -> bool {
+		// Particles can fall into the void, but not through the solid edge
+		let Some((x, y)) = self.wrap_pos(x, y)
 		else {
-			let pos = (pt.x as usize - 1, (pt.y + 1.0) as usize);
-			if self.get_pmap(pos.0, pos.1).is_none() {
-				self.move_to(pt_id, pos.0 as f32, pos.1 as f32);
+			return self.edge_mode == EdgeMode::Void;
+		};
+		if !self.in_reach(x, y) || self.wall_blocks(pt_id, x, y) {
+			return false;
+		}
+
+		match self.get_id(x, y) {
+			None => true,
+			Some(other) => other == pt_id || self.can_displace(pt_id, other)
+		}
+	}
+
+	/// Moves particle to x, y if the spot is empty or occupied by something it can displace
+	/// Returns true if moved
+	fn try_move(&mut self, pt_id: ParticleHandle, x: isize, y: isize) -> bool {
+		let Some((bx, by)) = self.wrap_pos(x, y)
+		else {
+			if self.edge_mode == EdgeMode::Solid {
+				return false;
+			}
+			// Void, move_to kills the particle
+			self.move_to(pt_id, x as f32, y as f32);
+			return true;
+		};
+		if !self.in_reach(bx, by) || self.wall_blocks(pt_id, bx, by) {
+			return false;
+		}
+
+		match self.get_id(bx, by) {
+			None => {
+				self.move_to(pt_id, bx as f32, by as f32);
+				true
+			}
+			Some(other) if self.can_displace(pt_id, other) => {
+				self.swap_parts(pt_id, other);
+				true
 			}
+			Some(_) => false
 		}
 	}
 
-	pub fn step(&mut self) {
-		for pt_id in 0..self.parts.len() {
-			let part = &mut self.parts[pt_id];
-
-			let behaviour = part.get_type(&self.element_manager).behaviour;
-
-			match behaviour {
-				ElementBehaviour::Skip => {}
-				ElementBehaviour::Solid => {}
-				ElementBehaviour::Powder | ElementBehaviour::Fluid | ElementBehaviour::Gas => {
-					part.vy += 0.1; // Gravity
-					if self.velocity_move(pt_id) {
-						self.powder_move(pt_id);
-					}
-				}
+	/// True if a wall at x, y keeps the particle out
+	pub(super) fn wall_blocks(&self, pt_id: ParticleHandle, x: usize, y: usize) -> bool {
+		let behaviour = self.parts[pt_id].get_type(self.element_manager).behaviour;
+		self.walls().get(x, y).blocks(behaviour)
+	}
+
+	/// Heavier powders and fluids sink through lighter fluids and gases
+	/// The displaced particle takes the place of the first one, so a wall there has to let it in
+	fn can_displace(&self, pt_id: ParticleHandle, other_id: ParticleHandle) -> bool {
+		let pt = &self.parts[pt_id];
+		let el = pt.get_type(self.element_manager);
+		let other = self.parts[other_id].get_type(self.element_manager);
+
+		let wall = self.walls().get(pt.x as usize, pt.y as usize);
+		if wall.blocks(other.behaviour) {
+			return false;
+		}
+
+		matches!(
+			el.behaviour,
+			ElementBehaviour::Powder | ElementBehaviour::Fluid
+		) && matches!(
+			other.behaviour,
+			ElementBehaviour::Fluid | ElementBehaviour::Gas
+		) && el.density > other.density
+	}
+
+	// Returns true if the particle was killed
+	fn apply_transitions(&mut self, pt_id: ParticleHandle) -> bool {
+		let pt = &self.parts[pt_id];
+		let el = pt.get_type(self.element_manager);
+		let pressure = self.air.pressure_at(pt.x as usize, pt.y as usize);
+
+		let transition = [
+			el.high_temp.filter(|t| pt.temp > t.threshold),
+			el.low_temp.filter(|t| pt.temp < t.threshold),
+			el.high_pressure.filter(|t| pressure > t.threshold),
+			el.low_pressure.filter(|t| pressure < t.threshold)
+		]
+		.into_iter()
+		.flatten()
+		.next();
+
+		match transition {
+			Some(Transition { into, .. }) => self.change_type(pt_id, into),
+			None => false
+		}
+	}
+
+	/// Energy particles overlap matter, swapping one would leave pmap pointing at it
+	pub(super) fn swap_parts(&mut self, a: ParticleHandle, b: ParticleHandle) -> bool {
+		let alive = |id| self.parts.get(id).is_some_and(|pt| pt.p_type != 0);
+		if !alive(a) || !alive(b) || self.is_energy(a) || self.is_energy(b) {
+			return false;
+		}
+
+		let (ax, ay) = (self.parts[a].x, self.parts[a].y);
+		let (bx, by) = (self.parts[b].x, self.parts[b].y);
+
+		self.parts[a].x = bx;
+		self.parts[a].y = by;
+		self.parts[b].x = ax;
+		self.parts[b].y = ay;
+
+		self.pmap[bx as usize + by as usize * self.width] = Some(a);
+		self.pmap[ax as usize + ay as usize * self.width] = Some(b);
+		true
+	}
+
+	/// Moves the particle between pmap and photons when it switches between matter and energy
+	pub(super) fn change_type(&mut self, pt_id: ParticleHandle, p_type: u16) -> bool {
+		if p_type == 0 {
+			self.kill_part(pt_id).expect("Tried to kill invalid part");
+			return true;
+		}
+
+		let was_energy = self.is_energy(pt_id);
+		let pt = &self.parts[pt_id];
+		let pos = pt.x as usize + pt.y as usize * self.width;
+		let old_type = pt.p_type;
+		self.parts[pt_id].p_type = p_type;
+		let energy = self.is_energy(pt_id);
+
+		if was_energy && !energy {
+			if self.pmap[pos].is_some() {
+				self.parts[pt_id].p_type = old_type;
+				self.kill_part(pt_id).expect("Tried to kill invalid part");
+				return true;
+			}
+			if self.photons[pos] == Some(pt_id) {
+				self.photons[pos] = None;
 			}
+			self.pmap[pos] = Some(pt_id);
 		}
+		else if !was_energy && energy {
+			self.pmap[pos] = None;
+			self.photons[pos] = Some(pt_id);
+		}
+		false
+	}
 
-		self.update_p_map();
+	/// Updates particles that were in the chunk at the start of the tick and are still in its region
+	pub(super) fn update_chunk(&mut self) {
+		for i in 0..self.chunk_parts().len() {
+			let pt_id = self.chunk_parts()[i];
+			if self.parts.owns(pt_id) {
+				self.update_part(pt_id);
+			}
+		}
+	}
+
+	fn update_part(&mut self, pt_id: ParticleHandle) {
+		if self.parts[pt_id].p_type == 0 ||
+			self.apply_transitions(pt_id) ||
+			self.update_electricity(pt_id) ||
+			self.update_combustion(pt_id) ||
+			self.update_explosives(pt_id)
+		{
+			return;
+		}
+
+		if let Some(update) = self.parts[pt_id].get_type(self.element_manager).update {
+			update(&mut UpdateContext::new(self, pt_id));
+			if self.parts[pt_id].p_type == 0 {
+				return;
+			}
+		}
+
+		if self.apply_reactions(pt_id) {
+			return;
+		}
+
+		let part = &mut self.parts[pt_id];
+
+		let el = part.get_type(self.element_manager);
+		let behaviour = el.behaviour;
+		let (gx, gy) = self.gravity.at(part.x, part.y);
+		let (gx, gy) = (gx * el.gravity, gy * el.gravity);
+
+		match behaviour {
+			ElementBehaviour::Skip => {}
+			ElementBehaviour::Solid => {}
+			ElementBehaviour::Powder => {
+				part.vx += gx;
+				part.vy += gy;
+				if self.velocity_move(pt_id) {
+					self.powder_move(pt_id);
+				}
+			}
+			ElementBehaviour::Fluid => {
+				part.vx += gx;
+				part.vy += gy;
+				let collided = self.velocity_move(pt_id);
+				if self.parts[pt_id].p_type == 0 {
+					return;
+				}
+
+				// Resting fluid doesn't collide every tick, so check what's below too
+				let pt = &self.parts[pt_id];
+				let (x, y) = (pt.x as isize, pt.y as isize);
+				let resting = self
+					.fall_dir(pt_id)
+					.map(|down| DIRECTIONS[down])
+					.is_some_and(|(dx, dy)| !self.can_move_to(pt_id, x + dx, y + dy));
+				if (collided || resting) && !self.powder_move(pt_id) {
+					self.fluid_move(pt_id);
+				}
+			}
+			ElementBehaviour::Gas => self.gas_move(pt_id),
+			ElementBehaviour::Energy => self.energy_move(pt_id)
+		}
 	}
 }
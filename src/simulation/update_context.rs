@@ -0,0 +1,144 @@
+use crate::simulation::elements::ElementManager;
+use crate::simulation::heat::{MAX_TEMP, MIN_TEMP};
+use crate::simulation::properties::{PropKind, Property};
+use crate::simulation::region::Region;
+use crate::simulation::rng::SimRng;
+use crate::simulation::sim::ParticleHandle;
+use crate::simulation::Particle;
+
+/// Passed to `Element::update`, gives access to the updated particle and its surroundings
+/// Positions are relative to the updated particle
+/// Other chunks are updated at the same time, so positions and particles more than a few pixels away are out of reach
+/// Out of reach ones are treated as missing
+/// Position and type only change through `create`, `kill`, `change_type` and `swap`, so pmap stays in sync
+pub struct UpdateContext<'a, 'b> {
+	region: &'a mut Region<'b>,
+	id:     ParticleHandle
+}
+
+impl<'a, 'b> UpdateContext<'a, 'b> {
+	pub(super) fn new(region: &'a mut Region<'b>, id: ParticleHandle) -> Self {
+		Self { region, id }
+	}
+
+	pub fn id(&self) -> ParticleHandle {
+		self.id
+	}
+
+	pub fn part(&self) -> &Particle {
+		&self.region.parts[self.id]
+	}
+
+	/// Use this instead of thread_rng so the simulation stays deterministic
+	pub fn rng(&mut self) -> &mut SimRng {
+		self.region.rng()
+	}
+
+	pub fn elements(&self) -> &ElementManager {
+		self.region.element_manager
+	}
+
+	pub fn neighbour_id(&self, dx: isize, dy: isize) -> Option<ParticleHandle> {
+		let (x, y) = self.offset(dx, dy)?;
+		self.region.get_id(x, y)
+	}
+
+	pub fn neighbour(&self, dx: isize, dy: isize) -> Option<&Particle> {
+		let id = self.neighbour_id(dx, dy)?;
+		self.region.parts.get(id)
+	}
+
+	/// Returns a live particle by id, for example one from `neighbour_id`
+	pub fn get(&self, id: ParticleHandle) -> Option<&Particle> {
+		self.region.parts.get(id).filter(|pt| pt.p_type != 0)
+	}
+
+	/// Sets a slot property like `LIFE`, element properties only take ids with an element
+	/// Returns false if the value was rejected or the particle isn't alive
+	pub fn set(&mut self, id: ParticleHandle, prop: Property, value: u16) -> bool {
+		if prop.kind == PropKind::Element && self.elements().elements.get(value as usize).is_none()
+		{
+			return false;
+		}
+
+		let Some(pt) = self.get_mut(id)
+		else {
+			return false;
+		};
+		prop.set(pt, value);
+		true
+	}
+
+	/// Temperature is clamped to what the simulation supports, NaN is rejected
+	/// Returns false if the value was rejected or the particle isn't alive
+	pub fn set_temp(&mut self, id: ParticleHandle, temp: f32) -> bool {
+		if temp.is_nan() {
+			return false;
+		}
+
+		let Some(pt) = self.get_mut(id)
+		else {
+			return false;
+		};
+		pt.temp = temp.clamp(MIN_TEMP, MAX_TEMP);
+		true
+	}
+
+	/// Returns false if either component isn't finite or the particle isn't alive
+	pub fn set_velocity(&mut self, id: ParticleHandle, vx: f32, vy: f32) -> bool {
+		if !vx.is_finite() || !vy.is_finite() {
+			return false;
+		}
+
+		let Some(pt) = self.get_mut(id)
+		else {
+			return false;
+		};
+		pt.vx = vx;
+		pt.vy = vy;
+		true
+	}
+
+	/// Creates a particle with the element's default properties, fails if the spot is taken
+	pub fn create(&mut self, dx: isize, dy: isize, p_type: u16) -> Option<ParticleHandle> {
+		let (x, y) = self.offset(dx, dy)?;
+		self.region.create_part(p_type, x, y)
+	}
+
+	/// Returns false if the particle isn't alive or is out of reach
+	pub fn kill(&mut self, id: ParticleHandle) -> bool {
+		self.region.kill_part(id).is_ok()
+	}
+
+	pub fn kill_self(&mut self) {
+		self.region.kill_part(self.id).ok();
+	}
+
+	/// Changing to type 0 kills the particle, types without an element are rejected
+	/// Returns false if the type was rejected or the particle isn't alive
+	pub fn change_type(&mut self, id: ParticleHandle, p_type: u16) -> bool {
+		if self.get(id).is_none() || self.elements().elements.get(p_type as usize).is_none() {
+			return false;
+		}
+
+		self.region.change_type(id, p_type);
+		true
+	}
+
+	/// Energy particles can't be swapped
+	/// Returns false if either particle isn't alive or is energy
+	pub fn swap(&mut self, a: ParticleHandle, b: ParticleHandle) -> bool {
+		self.get(a).is_some() && self.get(b).is_some() && self.region.swap_parts(a, b)
+	}
+
+	fn get_mut(&mut self, id: ParticleHandle) -> Option<&mut Particle> {
+		self.region.parts.get_mut(id).filter(|pt| pt.p_type != 0)
+	}
+
+	fn offset(&self, dx: isize, dy: isize) -> Option<(usize, usize)> {
+		let pt = self.part();
+		self.region
+			.wrap_pos(pt.x as isize + dx, pt.y as isize + dy)
+			.filter(|&(x, y)| self.region.in_reach(x, y))
+	}
+}
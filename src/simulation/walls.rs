@@ -0,0 +1,204 @@
+use crate::simulation::air::CELL;
+use crate::simulation::electricity::{COOLDOWN, SPARK_LIFE};
+use crate::simulation::elements::{ElementBehaviour, EL_SPRK};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum WallType {
+	None,
+	Wall,           // Blocks particles and air
+	BlockParticles, // Air passes through
+	BlockAir,       // Particles pass through
+	AllowPowder,    // Blocks all other particles, air passes through
+	AllowFluid,
+	AllowGas,
+	Conductor // Blocks particles and air, carries sparks
+}
+
+impl WallType {
+	pub const ALL: [WallType; 8] = [
+		WallType::None,
+		WallType::Wall,
+		WallType::BlockParticles,
+		WallType::BlockAir,
+		WallType::AllowPowder,
+		WallType::AllowFluid,
+		WallType::AllowGas,
+		WallType::Conductor
+	];
+
+	/// True if particles with this behaviour can't enter the wall
+	pub fn blocks(self, behaviour: ElementBehaviour) -> bool {
+		match self {
+			WallType::None | WallType::BlockAir => false,
+			WallType::Wall | WallType::BlockParticles | WallType::Conductor => true,
+			WallType::AllowPowder => !matches!(behaviour, ElementBehaviour::Powder),
+			WallType::AllowFluid => !matches!(behaviour, ElementBehaviour::Fluid),
+			WallType::AllowGas => !matches!(behaviour, ElementBehaviour::Gas)
+		}
+	}
+
+	pub fn blocks_air(self) -> bool {
+		matches!(
+			self,
+			WallType::Wall | WallType::BlockAir | WallType::Conductor
+		)
+	}
+
+	/// Alpha is how much of the wall shows, walls are only drawn on empty pixels
+	pub fn color(self) -> (u8, u8, u8, u8) {
+		match self {
+			WallType::None => (0, 0, 0, 0),
+			WallType::Wall => (128, 128, 128, 255),
+			WallType::BlockParticles => (160, 64, 64, 255),
+			WallType::BlockAir => (128, 128, 255, 96),
+			WallType::AllowPowder => (192, 160, 96, 160),
+			WallType::AllowFluid => (64, 96, 224, 160),
+			WallType::AllowGas => (192, 192, 192, 96),
+			WallType::Conductor => (200, 160, 64, 255)
+		}
+	}
+}
+
+/// Walls on a grid of `CELL`x`CELL` sim pixels, the same grid as `Air`
+#[derive(Clone)]
+pub struct Walls {
+	cells:    Box<[WallType]>,
+	sparks:   Box<[u16]>, // Conductors only, ticks left of the spark and then of the cooldown
+	width:    usize,      // In cells
+	height:   usize,
+	revision: u64 /* Changes every time a wall or spark changes, so the renderer knows when to redraw */
+}
+
+impl Walls {
+	/// Takes the size of the simulation in sim pixels
+	pub fn new(width: usize, height: usize) -> Self {
+		let (width, height) = (width.div_ceil(CELL), height.div_ceil(CELL));
+
+		Self {
+			cells: vec![WallType::None; width * height].into_boxed_slice(),
+			sparks: vec![0; width * height].into_boxed_slice(),
+			width,
+			height,
+			revision: 0
+		}
+	}
+
+	/// Index of the cell containing sim position x, y
+	pub fn cell_index(&self, x: usize, y: usize) -> Option<usize> {
+		let (cx, cy) = (x / CELL, y / CELL);
+		if cx >= self.width || cy >= self.height {
+			return None;
+		}
+
+		Some(cx + cy * self.width)
+	}
+
+	/// Wall at sim position x, y
+	pub fn get(&self, x: usize, y: usize) -> WallType {
+		match self.cell_index(x, y) {
+			Some(i) => self.cells[i],
+			None => WallType::None
+		}
+	}
+
+	/// Returns true if the wall changed
+	pub(super) fn set(&mut self, x: usize, y: usize, wall: WallType) -> bool {
+		let Some(i) = self.cell_index(x, y).filter(|&i| self.cells[i] != wall)
+		else {
+			return false;
+		};
+
+		self.cells[i] = wall;
+		self.sparks[i] = 0;
+		self.revision += 1;
+		true
+	}
+
+	/// Takes the walls and sparks of other, which has to be the same size
+	pub(super) fn copy_from(&mut self, other: &Walls) {
+		self.cells.copy_from_slice(&other.cells);
+		self.sparks.copy_from_slice(&other.sparks);
+		self.revision += 1;
+	}
+
+	/// Sparks the conductor wall at sim position x, y
+	/// Returns false if there's no conductor or it was sparked recently
+	pub(super) fn spark(&mut self, x: usize, y: usize) -> bool {
+		if !self.can_spark(x, y) {
+			return false;
+		}
+
+		let i = self.cell_index(x, y).unwrap();
+		self.sparks[i] = SPARK_LIFE + COOLDOWN;
+		true
+	}
+
+	pub(super) fn can_spark(&self, x: usize, y: usize) -> bool {
+		self.cell_index(x, y)
+			.is_some_and(|i| self.cells[i] == WallType::Conductor && self.sparks[i] == 0)
+	}
+
+	/// Moves sparks one cell further and counts them down
+	/// Returns the cells sparked during the last tick, they spark conductors touching them
+	pub(super) fn update_sparks(&mut self) -> Vec<usize> {
+		if self.sparks.iter().all(|s| *s == 0) {
+			return Vec::new();
+		}
+
+		let fresh: Vec<usize> = (0..self.sparks.len())
+			.filter(|&i| self.sparks[i] == SPARK_LIFE + COOLDOWN)
+			.collect();
+		self.sparks
+			.iter_mut()
+			.for_each(|s| *s = s.saturating_sub(1));
+
+		for &i in &fresh {
+			let (x, y) = (i % self.width, i / self.width);
+			let neighbours = [
+				(x > 0).then(|| i - 1),
+				(x + 1 < self.width).then(|| i + 1),
+				(y > 0).then(|| i - self.width),
+				(y + 1 < self.height).then(|| i + self.width)
+			];
+
+			for j in neighbours.into_iter().flatten() {
+				if self.cells[j] == WallType::Conductor && self.sparks[j] == 0 {
+					self.sparks[j] = SPARK_LIFE + COOLDOWN;
+				}
+			}
+		}
+
+		self.revision += 1;
+		fresh
+	}
+
+	pub fn is_sparked(&self, i: usize) -> bool {
+		self.sparks[i] > COOLDOWN
+	}
+
+	/// Color of the cell for rendering, sparked conductors light up
+	pub fn get_cell_color(&self, i: usize) -> (u8, u8, u8, u8) {
+		if self.is_sparked(i) {
+			let col = EL_SPRK.col;
+			return (col[0], col[1], col[2], 255);
+		}
+		self.cells[i].color()
+	}
+
+	/// Wall by cell index, cells are in rows of `get_width`
+	pub fn get_cell(&self, i: usize) -> WallType {
+		self.cells[i]
+	}
+
+	pub fn get_width(&self) -> usize {
+		self.width
+	}
+
+	pub fn get_height(&self) -> usize {
+		self.height
+	}
+
+	pub fn get_revision(&self) -> u64 {
+		self.revision
+	}
+}